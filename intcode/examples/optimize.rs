@@ -0,0 +1,45 @@
+//! Optimizes an intcode program, printing each rewrite and checking the result
+//! against the original for every given input.
+//!
+//! Usage: `cargo run -p intcode --example optimize -- <program> [input...]`
+
+use intcode::optimize::{optimize, verify};
+
+fn main() {
+    let args = std::env::args().collect::<Vec<String>>();
+    if args.len() < 2 {
+        eprintln!("Usage: {} <program> [input...]", args[0]);
+        std::process::exit(2);
+    }
+
+    let program = std::fs::read_to_string(&args[1])
+        .expect("Failed to read input file")
+        .parse::<intcode::Program<i64>>()
+        .expect("Failed to parse input");
+
+    let inputs = args[2..]
+        .iter()
+        .map(|input| vec![input.parse::<i64>().expect("Inputs must be integers")])
+        .collect::<Vec<Vec<i64>>>();
+
+    let optimization = match optimize(&program) {
+        Ok(optimization) => optimization,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
+
+    for rewrite in &optimization.rewrites {
+        println!("{}", rewrite);
+    }
+    println!("{} rewrites", optimization.rewrites.len());
+
+    match verify(&program, &optimization.program, &inputs) {
+        Ok(()) => println!("Verified against {} inputs", inputs.len()),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
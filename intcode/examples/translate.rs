@@ -0,0 +1,44 @@
+//! Prints the Rust translation of an intcode program.
+//!
+//! Usage: `cargo run -p intcode --example translate -- <program> <module> [i32|i64|i128]`
+
+fn translate<T: intcode::Word>(code: &str, name: &str) -> Result<String, String> {
+    let program = code
+        .parse::<intcode::Program<T>>()
+        .map_err(|err| err.to_string())?;
+
+    let translation = intcode::translate::translate(&program, name)?;
+    eprintln!(
+        "Compiled {} instructions of {}",
+        translation.compiled.len(),
+        name
+    );
+
+    Ok(translation.source)
+}
+
+fn main() {
+    let args = std::env::args().collect::<Vec<String>>();
+    if args.len() < 3 {
+        eprintln!("Usage: {} <program> <module> [i32|i64|i128]", args[0]);
+        std::process::exit(2);
+    }
+
+    let code = std::fs::read_to_string(&args[1]).expect("Failed to read input file");
+    let name = &args[2];
+
+    let source = match args.get(3).map(String::as_str) {
+        None | Some("i32") => translate::<i32>(&code, name),
+        Some("i64") => translate::<i64>(&code, name),
+        Some("i128") => translate::<i128>(&code, name),
+        Some(word) => Err(format!("Unsupported word type {}", word)),
+    };
+
+    match source {
+        Ok(source) => print!("{}", source),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
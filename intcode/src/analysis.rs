@@ -0,0 +1,196 @@
+use super::extension::Extensions;
+use super::{OpCode, ParamterMode, Word};
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// An instruction decoded from a program's initial memory.
+#[derive(Debug)]
+pub(crate) struct Instruction<T> {
+    pub(crate) address: usize,
+    pub(crate) opcode: OpCode,
+    pub(crate) parameters: Vec<T>,
+}
+
+/// Where control can go after an instruction has executed.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Target {
+    /// A jump to a fixed address.
+    Static(usize),
+    /// A jump to the address held in the given cell.
+    Computed(usize),
+    /// A jump to a value that is not a valid address.
+    Invalid,
+}
+
+impl<T: Word> Instruction<T> {
+    /// Decodes the built-in instruction at `address`, if there is a complete one.
+    pub(crate) fn decode(memory: &[T], address: usize) -> Option<Instruction<T>> {
+        let opcode = OpCode::decode(*memory.get(address)?, &Extensions::new()).ok()?;
+        let count = match &opcode {
+            OpCode::EXIT => 0,
+            OpCode::INPUT | OpCode::OUTPUT(_) => 1,
+            OpCode::TJUMP(..) | OpCode::FJUMP(..) => 2,
+            OpCode::ADD(..) | OpCode::MULTIPLY(..) | OpCode::LESSTHAN(..) | OpCode::EQUALS(..) => 3,
+            OpCode::EXTENSION(..) => return None,
+        };
+        let parameters = memory.get(address + 1..address + 1 + count)?.to_vec();
+
+        Some(Instruction {
+            address,
+            opcode,
+            parameters,
+        })
+    }
+
+    pub(crate) fn cells(&self) -> Range<usize> {
+        self.address..self.next()
+    }
+
+    /// Address of the instruction directly after this one.
+    pub(crate) fn next(&self) -> usize {
+        self.address + 1 + self.parameters.len()
+    }
+
+    /// The cell this instruction writes to, if any.
+    pub(crate) fn write_target(&self) -> Option<usize> {
+        match self.opcode {
+            OpCode::INPUT => self.parameters[0].to_address().ok(),
+            OpCode::ADD(..) | OpCode::MULTIPLY(..) | OpCode::LESSTHAN(..) | OpCode::EQUALS(..) => {
+                self.parameters[2].to_address().ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// The cells this instruction reads through position mode parameters.
+    pub(crate) fn reads(&self) -> Vec<usize> {
+        self.modes()
+            .iter()
+            .zip(self.parameters.iter())
+            .filter(|(mode, _)| matches!(mode, ParamterMode::POSITION))
+            .filter_map(|(_, parameter)| parameter.to_address().ok())
+            .collect()
+    }
+
+    /// Modes of the parameters that are read, in order.
+    pub(crate) fn modes(&self) -> Vec<&ParamterMode> {
+        match &self.opcode {
+            OpCode::OUTPUT(mode_1) => vec![mode_1],
+            OpCode::ADD(mode_1, mode_2)
+            | OpCode::MULTIPLY(mode_1, mode_2)
+            | OpCode::LESSTHAN(mode_1, mode_2)
+            | OpCode::EQUALS(mode_1, mode_2)
+            | OpCode::TJUMP(mode_1, mode_2)
+            | OpCode::FJUMP(mode_1, mode_2) => vec![mode_1, mode_2],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Whether execution may continue with the next instruction and where it
+    /// may jump to. Jumps with an immediate condition only take the branch
+    /// the condition selects.
+    pub(crate) fn flow(&self) -> (bool, Option<Target>) {
+        let (mode_1, mode_2, jump_if_zero) = match &self.opcode {
+            OpCode::EXIT => return (false, None),
+            OpCode::TJUMP(mode_1, mode_2) => (mode_1, mode_2, false),
+            OpCode::FJUMP(mode_1, mode_2) => (mode_1, mode_2, true),
+            _ => return (true, None),
+        };
+
+        let target = match mode_2 {
+            ParamterMode::IMMEDIATE => match self.parameters[1].to_address() {
+                Ok(address) => Target::Static(address),
+                Err(_) => Target::Invalid,
+            },
+            ParamterMode::POSITION => match self.parameters[1].to_address() {
+                Ok(address) => Target::Computed(address),
+                Err(_) => Target::Invalid,
+            },
+        };
+
+        match mode_1 {
+            ParamterMode::IMMEDIATE => {
+                if (self.parameters[0] == T::from(0)) == jump_if_zero {
+                    (false, Some(target))
+                } else {
+                    (true, None)
+                }
+            }
+            ParamterMode::POSITION => (true, Some(target)),
+        }
+    }
+}
+
+/// Decodes every instruction reachable from `seeds` by following the static
+/// control flow, and reports whether any of them jumps to a computed address.
+pub(crate) fn reachable<T: Word>(
+    memory: &[T],
+    seeds: impl IntoIterator<Item = usize>,
+) -> (BTreeMap<usize, Instruction<T>>, bool) {
+    let mut instructions = BTreeMap::new();
+    let mut computed = false;
+    let mut pending = seeds.into_iter().collect::<Vec<usize>>();
+
+    while let Some(address) = pending.pop() {
+        if instructions.contains_key(&address) {
+            continue;
+        }
+
+        let instruction = match Instruction::decode(memory, address) {
+            Some(instruction) => instruction,
+            None => continue,
+        };
+
+        let (falls_through, target) = instruction.flow();
+        if falls_through {
+            pending.push(instruction.next());
+        }
+        match target {
+            Some(Target::Static(target)) => pending.push(target),
+            Some(Target::Computed(_)) => computed = true,
+            _ => {}
+        }
+
+        instructions.insert(address, instruction);
+    }
+
+    (instructions, computed)
+}
+
+#[cfg(test)]
+mod instructions {
+    use super::*;
+
+    #[test]
+    fn follows_jumps() {
+        // Jumps over the data at 3..=5 straight to the exit.
+        let memory = vec![1105, 1, 6, 42, 43, 44, 99];
+        let (instructions, computed) = reachable(&memory, vec![0]);
+
+        assert_eq!(
+            instructions.keys().copied().collect::<Vec<usize>>(),
+            vec![0, 6]
+        );
+        assert!(!computed);
+    }
+
+    #[test]
+    fn computed_jump() {
+        let memory = vec![3, 6, 105, 1, 6, 99, 5];
+        let (instructions, computed) = reachable(&memory, vec![0]);
+
+        assert_eq!(
+            instructions.keys().copied().collect::<Vec<usize>>(),
+            vec![0, 2]
+        );
+        assert!(computed);
+        assert_eq!(instructions[&0].write_target(), Some(6));
+        assert_eq!(instructions[&2].flow(), (false, Some(Target::Computed(6))));
+    }
+
+    #[test]
+    fn truncated_instruction() {
+        assert!(Instruction::decode(&[1, 0, 0], 0).is_none());
+        assert!(Instruction::decode(&[1, 0, 0, 0], 0).is_some());
+    }
+}
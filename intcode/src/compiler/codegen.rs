@@ -0,0 +1,252 @@
+use super::parser::{Expression, Name, Operator, Statement, StatementKind};
+use super::CompileError;
+use crate::Word;
+use std::collections::HashMap;
+
+/// A memory cell whose address is only known once all code is generated.
+#[derive(Debug, Clone, Copy)]
+enum Cell<T> {
+    Word(T),
+    Label(usize),
+    Variable(usize),
+    Temporary(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operand<T> {
+    Immediate(T),
+    Position(Cell<T>),
+}
+
+/// Lays out the code first, then one cell per variable and finally the
+/// temporaries used to evaluate nested expressions.
+pub(crate) fn generate<T: Word>(statements: &[Statement<T>]) -> Result<Vec<T>, CompileError> {
+    let mut generator = Generator {
+        code: Vec::new(),
+        positions: Vec::new(),
+        position: (1, 1),
+        labels: Vec::new(),
+        variables: HashMap::new(),
+        temporaries: 0,
+    };
+
+    generator.block(statements)?;
+    generator.emit(99, &[], None);
+
+    let variables = generator.code.len();
+    let temporaries = variables + generator.variables.len();
+    let size = temporaries + generator.temporaries;
+
+    // Every address up to the last temporary is used by some cell, so
+    // checking each cell's address covers the whole memory.
+    let mut int_codes = generator
+        .code
+        .iter()
+        .zip(generator.positions.iter())
+        .map(|(cell, &(line, column))| {
+            let address = match *cell {
+                Cell::Word(value) => return Ok(value),
+                Cell::Label(label) => generator.labels[label].unwrap(),
+                Cell::Variable(slot) => variables + slot,
+                Cell::Temporary(slot) => temporaries + slot,
+            };
+            T::from_address(address).map_err(|message| CompileError {
+                line,
+                column,
+                message,
+            })
+        })
+        .collect::<Result<Vec<T>, CompileError>>()?;
+    int_codes.resize(size, T::from(0));
+
+    Ok(int_codes)
+}
+
+struct Generator<T> {
+    code: Vec<Cell<T>>,
+    /// The line and column of the statement each cell of `code` belongs to.
+    positions: Vec<(usize, usize)>,
+    /// The line and column of the statement being generated.
+    position: (usize, usize),
+    labels: Vec<Option<usize>>,
+    variables: HashMap<String, usize>,
+    temporaries: usize,
+}
+
+impl<T: Word> Generator<T> {
+    fn label(&mut self) -> usize {
+        self.labels.push(None);
+        self.labels.len() - 1
+    }
+
+    fn place(&mut self, label: usize) {
+        self.labels[label] = Some(self.code.len());
+    }
+
+    fn declare(&mut self, name: &Name) -> Cell<T> {
+        let slot = self.variables.len();
+        Cell::Variable(*self.variables.entry(name.name.clone()).or_insert(slot))
+    }
+
+    fn lookup(&self, name: &Name) -> Result<Cell<T>, CompileError> {
+        match self.variables.get(&name.name) {
+            Some(&slot) => Ok(Cell::Variable(slot)),
+            None => Err(CompileError {
+                line: name.line,
+                column: name.column,
+                message: format!("Undefined variable '{}'", name.name),
+            }),
+        }
+    }
+
+    fn temporary(&mut self, depth: usize) -> Cell<T> {
+        self.temporaries = self.temporaries.max(depth + 1);
+        Cell::Temporary(depth)
+    }
+
+    fn emit(&mut self, opcode: u8, operands: &[Operand<T>], target: Option<Cell<T>>) {
+        let ten = T::from(10);
+        let mut instruction = T::from(0);
+
+        for operand in operands.iter().rev() {
+            let digit = match operand {
+                Operand::Position(_) => 0,
+                Operand::Immediate(_) => 1,
+            };
+            instruction = instruction * ten + T::from(digit);
+        }
+
+        self.code
+            .push(Cell::Word(instruction * T::from(100) + T::from(opcode)));
+        self.code
+            .extend(operands.iter().map(|operand| match *operand {
+                Operand::Immediate(value) => Cell::Word(value),
+                Operand::Position(cell) => cell,
+            }));
+        self.code.extend(target);
+        self.positions.resize(self.code.len(), self.position);
+    }
+
+    fn jump(&mut self, label: usize) {
+        let always = Operand::Immediate(T::from(1));
+        self.emit(5, &[always, Operand::Immediate(T::from(0))], None);
+        *self.code.last_mut().unwrap() = Cell::Label(label);
+    }
+
+    fn jump_unless(&mut self, condition: Operand<T>, label: usize) {
+        self.emit(6, &[condition, Operand::Immediate(T::from(0))], None);
+        *self.code.last_mut().unwrap() = Cell::Label(label);
+    }
+
+    fn block(&mut self, statements: &[Statement<T>]) -> Result<(), CompileError> {
+        statements
+            .iter()
+            .try_for_each(|statement| self.statement(statement))
+    }
+
+    fn statement(&mut self, statement: &Statement<T>) -> Result<(), CompileError> {
+        let outer = std::mem::replace(&mut self.position, (statement.line, statement.column));
+
+        match &statement.kind {
+            StatementKind::Let(name, expression) => {
+                let value = self.expression(expression, 0)?;
+                let target = self.declare(name);
+                self.emit(1, &[value, Operand::Immediate(T::from(0))], Some(target));
+            }
+            StatementKind::Assign(name, expression) => {
+                let target = self.lookup(name)?;
+                let value = self.expression(expression, 0)?;
+                self.emit(1, &[value, Operand::Immediate(T::from(0))], Some(target));
+            }
+            StatementKind::Read(name) => {
+                let target = self.declare(name);
+                self.emit(3, &[], Some(target));
+            }
+            StatementKind::Print(expression) => {
+                let value = self.expression(expression, 0)?;
+                self.emit(4, &[value], None);
+            }
+            StatementKind::If(condition, then, otherwise) => {
+                let (else_label, end_label) = (self.label(), self.label());
+
+                let condition = self.expression(condition, 0)?;
+                self.jump_unless(condition, else_label);
+                self.block(then)?;
+                if !otherwise.is_empty() {
+                    self.jump(end_label);
+                }
+                self.place(else_label);
+                self.block(otherwise)?;
+                self.place(end_label);
+            }
+            StatementKind::While(condition, body) => {
+                let (start_label, end_label) = (self.label(), self.label());
+
+                self.place(start_label);
+                let condition = self.expression(condition, 0)?;
+                self.jump_unless(condition, end_label);
+                self.block(body)?;
+                self.jump(start_label);
+                self.place(end_label);
+            }
+        }
+
+        self.position = outer;
+        Ok(())
+    }
+
+    /// Evaluates `expression`, storing intermediate results in temporaries
+    /// `depth` and above so that outer expressions keep theirs.
+    fn expression(
+        &mut self,
+        expression: &Expression<T>,
+        depth: usize,
+    ) -> Result<Operand<T>, CompileError> {
+        let (operator, left, right) = match expression {
+            Expression::Number(value) => return Ok(Operand::Immediate(*value)),
+            Expression::Variable(name) => return Ok(Operand::Position(self.lookup(name)?)),
+            Expression::Negate(operand) => {
+                let value = self.expression(operand, depth)?;
+                let target = self.temporary(depth);
+                self.emit(2, &[value, Operand::Immediate(-T::from(1))], Some(target));
+                return Ok(Operand::Position(target));
+            }
+            Expression::Binary(operator, left, right) => (operator, left, right),
+        };
+
+        let left = self.expression(left, depth)?;
+        let right = self.expression(right, depth + 1)?;
+        let target = self.temporary(depth);
+
+        match operator {
+            Operator::ADD => self.emit(1, &[left, right], Some(target)),
+            Operator::MULTIPLY => self.emit(2, &[left, right], Some(target)),
+            Operator::SUBTRACT => {
+                let negated = match right {
+                    Operand::Immediate(value) => Operand::Immediate(-value),
+                    Operand::Position(_) => {
+                        let negated = self.temporary(depth + 1);
+                        self.emit(2, &[right, Operand::Immediate(-T::from(1))], Some(negated));
+                        Operand::Position(negated)
+                    }
+                };
+                self.emit(1, &[left, negated], Some(target));
+            }
+            Operator::LESSTHAN => self.emit(7, &[left, right], Some(target)),
+            Operator::GREATERTHAN => self.emit(7, &[right, left], Some(target)),
+            Operator::EQUALS => self.emit(8, &[left, right], Some(target)),
+            Operator::LESSEQUAL | Operator::GREATEREQUAL | Operator::NOTEQUALS => {
+                let (opcode, operands) = match operator {
+                    Operator::LESSEQUAL => (7, [right, left]),
+                    Operator::GREATEREQUAL => (7, [left, right]),
+                    _ => (8, [left, right]),
+                };
+                self.emit(opcode, &operands, Some(target));
+                let zero = Operand::Immediate(T::from(0));
+                self.emit(8, &[Operand::Position(target), zero], Some(target));
+            }
+        }
+
+        Ok(Operand::Position(target))
+    }
+}
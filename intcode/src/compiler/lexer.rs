@@ -0,0 +1,115 @@
+use super::CompileError;
+
+const KEYWORDS: [&str; 6] = ["let", "read", "print", "if", "else", "while"];
+const SYMBOLS: [&str; 15] = [
+    "<=", ">=", "==", "!=", "+", "-", "*", "(", ")", "{", "}", ";", "=", "<", ">",
+];
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum TokenKind {
+    Number(String),
+    Identifier(String),
+    Keyword(&'static str),
+    Symbol(&'static str),
+    End,
+}
+
+impl std::fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenKind::Number(digits) => write!(f, "{}", digits),
+            TokenKind::Identifier(name) => write!(f, "'{}'", name),
+            TokenKind::Keyword(text) | TokenKind::Symbol(text) => write!(f, "'{}'", text),
+            TokenKind::End => write!(f, "end of input"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Token {
+    pub(crate) kind: TokenKind,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+impl Token {
+    pub(crate) fn error(&self, message: String) -> CompileError {
+        CompileError {
+            line: self.line,
+            column: self.column,
+            message,
+        }
+    }
+}
+
+/// Splits `source` into tokens, always ending with `TokenKind::End`.
+pub(crate) fn tokenize(source: &str) -> Result<Vec<Token>, CompileError> {
+    let chars = source.chars().collect::<Vec<char>>();
+    let mut tokens = Vec::new();
+    let (mut line, mut column) = (1, 1);
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = (line, column);
+
+        let length = if c == '\n' {
+            line += 1;
+            column = 1;
+            i += 1;
+            continue;
+        } else if c.is_whitespace() {
+            1
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            chars[i..].iter().take_while(|&&c| c != '\n').count()
+        } else if c.is_ascii_digit() {
+            let digits = chars[i..].iter().take_while(|c| c.is_ascii_digit()).count();
+            let text = chars[i..i + digits].iter().collect::<String>();
+            tokens.push((TokenKind::Number(text), start));
+            digits
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let length = chars[i..]
+                .iter()
+                .take_while(|c| c.is_ascii_alphanumeric() || **c == '_')
+                .count();
+            let text = chars[i..i + length].iter().collect::<String>();
+            let kind = match KEYWORDS.iter().find(|&&keyword| keyword == text) {
+                Some(keyword) => TokenKind::Keyword(keyword),
+                None => TokenKind::Identifier(text),
+            };
+            tokens.push((kind, start));
+            length
+        } else {
+            let symbol = SYMBOLS.iter().find(|symbol| {
+                symbol
+                    .chars()
+                    .enumerate()
+                    .all(|(offset, s)| chars.get(i + offset) == Some(&s))
+            });
+
+            match symbol {
+                Some(symbol) => {
+                    tokens.push((TokenKind::Symbol(symbol), start));
+                    symbol.len()
+                }
+                None => {
+                    return Err(CompileError {
+                        line,
+                        column,
+                        message: format!("Unexpected character '{}'", c),
+                    })
+                }
+            }
+        };
+
+        i += length;
+        column += length;
+    }
+
+    tokens.push((TokenKind::End, (line, column)));
+
+    Ok(tokens
+        .into_iter()
+        .map(|(kind, (line, column))| Token { kind, line, column })
+        .collect())
+}
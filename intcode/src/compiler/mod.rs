@@ -0,0 +1,218 @@
+//! Compiler for a tiny language targeting intcode.
+//!
+//! ```text
+//! // Prints the factorial of every number read until a zero.
+//! read n;
+//! while n != 0 {
+//!     let result = 1;
+//!     while n > 1 {
+//!         result = result * n;
+//!         n = n - 1;
+//!     }
+//!     print result;
+//!     read n;
+//! }
+//! ```
+//!
+//! Statements are `let name = expression;`, assignments, `read name;`,
+//! `print expression;`, `if`/`else` and `while`. Expressions support integer
+//! literals, variables, parentheses, unary `-`, `+`, `-`, `*` and the
+//! comparisons `<`, `<=`, `>`, `>=`, `==` and `!=`, which evaluate to 1 or 0.
+//! Conditions are true when non-zero. All variables are global and start out
+//! as zero; `read` declares its variable if needed.
+
+mod codegen;
+mod lexer;
+mod parser;
+
+use super::{Program, Word};
+
+#[derive(Debug, PartialEq)]
+pub struct CompileError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for CompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+/// Compiles `source` into a program ready for `Program::execute`.
+pub fn compile<T: Word>(source: &str) -> Result<Program<T>, CompileError> {
+    let tokens = lexer::tokenize(source)?;
+    let statements = parser::parse::<T>(&tokens)?;
+    let int_codes = codegen::generate(&statements)?;
+
+    Ok(Program::new(int_codes))
+}
+
+#[cfg(test)]
+mod language {
+    use super::*;
+    use crate::analysis::Instruction;
+    use crate::optimize::{optimize, verify};
+    use crate::ProgramState;
+    use std::collections::BTreeSet;
+
+    fn run(source: &str, input: &[i32]) -> Vec<i32> {
+        let mut program = compile::<i32>(source).unwrap();
+        input
+            .iter()
+            .for_each(|&value| program.push_back_input(value));
+
+        assert_eq!(program.execute(), ProgramState::Complete);
+        std::iter::from_fn(|| program.pop_output()).collect()
+    }
+
+    fn error(source: &str) -> (usize, usize, String) {
+        let err = compile::<i32>(source).unwrap_err();
+        (err.line, err.column, err.message)
+    }
+
+    const FACTORIAL: &str = "
+        // Prints the factorial of every number read until a zero.
+        read n;
+        while n != 0 {
+            let result = 1;
+            while n > 1 {
+                result = result * n;
+                n = n - 1;
+            }
+            print result;
+            read n;
+        }";
+
+    #[test]
+    fn factorial() {
+        assert_eq!(run(FACTORIAL, &[5, 1, 10, 0]), vec![120, 1, 3_628_800]);
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(run("print 2 + 3 * 4;", &[]), vec![14]);
+        assert_eq!(run("print (2 + 3) * 4;", &[]), vec![20]);
+        assert_eq!(run("print 10 - 4 - 3;", &[]), vec![3]);
+        assert_eq!(run("let x = 7; print -x * -2;", &[]), vec![14]);
+        assert_eq!(run("read a; read b; print a - b;", &[3, 10]), vec![-7]);
+    }
+
+    #[test]
+    fn comparisons() {
+        let source = "read a; read b;
+                      print a < b; print a <= b; print a > b;
+                      print a >= b; print a == b; print a != b;";
+
+        assert_eq!(run(source, &[1, 2]), vec![1, 1, 0, 0, 0, 1]);
+        assert_eq!(run(source, &[2, 2]), vec![0, 1, 0, 1, 1, 0]);
+        assert_eq!(run(source, &[3, 2]), vec![0, 0, 1, 1, 0, 1]);
+    }
+
+    #[test]
+    fn if_else_chains() {
+        let source = "read x;
+                      if x < 8 { print 999; }
+                      else if x == 8 { print 1000; }
+                      else { print 1001; }";
+
+        assert_eq!(run(source, &[7]), vec![999]);
+        assert_eq!(run(source, &[8]), vec![1000]);
+        assert_eq!(run(source, &[9]), vec![1001]);
+    }
+
+    #[test]
+    fn nested_expressions_keep_temporaries() {
+        let source = "let a = 2; let b = 3;
+                      print (a * b + 1) * (a + b * (a - b)) - (b <= a);";
+
+        assert_eq!(run(source, &[]), vec![-7]);
+    }
+
+    #[test]
+    fn uses_every_instruction() {
+        let program = compile::<i32>(FACTORIAL).unwrap();
+
+        // The code runs straight to the final halt, with the variables after it.
+        let mut opcodes = BTreeSet::new();
+        let mut address = 0;
+        while let Some(instruction) = Instruction::decode(&program.int_codes, address) {
+            opcodes.insert(program.int_codes[address] % 100);
+            if program.int_codes[address] == 99 {
+                break;
+            }
+            address = instruction.next();
+        }
+
+        assert_eq!(
+            opcodes,
+            [1, 2, 3, 4, 5, 6, 7, 8, 99].iter().copied().collect()
+        );
+    }
+
+    #[test]
+    fn wide_words() {
+        let mut program = compile::<i128>("read x; print x * x * x;").unwrap();
+        program.push_back_input(1_000_000_000_000);
+
+        program.execute();
+        assert_eq!(
+            program.pop_output(),
+            Some(1_000_000_000_000_000_000_000_000_000_000_000_000)
+        );
+    }
+
+    #[test]
+    fn optimizes() {
+        let program = compile::<i32>(
+            "let limit = 2 * 5; read x;
+             while x < limit { print x * 1; x = x + 3; }",
+        )
+        .unwrap();
+        let optimized = optimize(&program).unwrap();
+
+        assert!(!optimized.rewrites.is_empty());
+
+        let inputs = (-2..12).map(|x| vec![x]).collect::<Vec<Vec<i32>>>();
+        assert_eq!(verify(&program, &optimized.program, &inputs), Ok(()));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            error("print x;"),
+            (1, 7, "Undefined variable 'x'".to_string())
+        );
+        assert_eq!(
+            error("let x = 1;\nx = x / 2;"),
+            (2, 7, "Unexpected character '/'".to_string())
+        );
+        assert_eq!(
+            error("while 1 {\n  print 1;\n"),
+            (3, 1, "Expected '}', but found end of input".to_string())
+        );
+        assert_eq!(
+            error("print 1 +;"),
+            (1, 10, "Expected an expression, but found ';'".to_string())
+        );
+        assert_eq!(
+            error("print 99999999999;"),
+            (1, 7, "Invalid number 99999999999".to_string())
+        );
+
+        // 16384 two cell prints fill every address an i16 can hold.
+        let source = "print 1;\n".repeat(16384) + "print 2;\n  let x = 3;";
+        let err = compile::<i16>(&source).unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.message),
+            (
+                16386,
+                3,
+                "Address 32775 does not fit into a word".to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use super::lexer::{Token, TokenKind};
+use super::CompileError;
+use crate::Word;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum Operator {
+    ADD,
+    SUBTRACT,
+    MULTIPLY,
+    LESSTHAN,
+    LESSEQUAL,
+    GREATERTHAN,
+    GREATEREQUAL,
+    EQUALS,
+    NOTEQUALS,
+}
+
+/// A variable reference, keeping its position for error reporting.
+#[derive(Debug)]
+pub(crate) struct Name {
+    pub(crate) name: String,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+#[derive(Debug)]
+pub(crate) enum Expression<T> {
+    Number(T),
+    Variable(Name),
+    Negate(Box<Expression<T>>),
+    Binary(Operator, Box<Expression<T>>, Box<Expression<T>>),
+}
+
+/// A statement and the position of its first token.
+#[derive(Debug)]
+pub(crate) struct Statement<T> {
+    pub(crate) kind: StatementKind<T>,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+#[derive(Debug)]
+pub(crate) enum StatementKind<T> {
+    Let(Name, Expression<T>),
+    Assign(Name, Expression<T>),
+    Read(Name),
+    Print(Expression<T>),
+    If(Expression<T>, Vec<Statement<T>>, Vec<Statement<T>>),
+    While(Expression<T>, Vec<Statement<T>>),
+}
+
+pub(crate) fn parse<T: Word>(tokens: &[Token]) -> Result<Vec<Statement<T>>, CompileError> {
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+    };
+    let mut statements = Vec::new();
+
+    while parser.peek().kind != TokenKind::End {
+        statements.push(parser.statement()?);
+    }
+
+    Ok(statements)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> &'a Token {
+        &self.tokens[self.position]
+    }
+
+    fn advance(&mut self) -> &'a Token {
+        let token = self.peek();
+        if token.kind != TokenKind::End {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, symbol: &str) -> bool {
+        match self.peek().kind {
+            TokenKind::Symbol(s) | TokenKind::Keyword(s) if s == symbol => {
+                self.advance();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), CompileError> {
+        if self.eat(symbol) {
+            Ok(())
+        } else {
+            let token = self.peek();
+            Err(token.error(format!("Expected '{}', but found {}", symbol, token.kind)))
+        }
+    }
+
+    fn name(&mut self) -> Result<Name, CompileError> {
+        let token = self.advance();
+        match &token.kind {
+            TokenKind::Identifier(name) => Ok(Name {
+                name: name.clone(),
+                line: token.line,
+                column: token.column,
+            }),
+            kind => Err(token.error(format!("Expected a variable, but found {}", kind))),
+        }
+    }
+
+    fn statement<T: Word>(&mut self) -> Result<Statement<T>, CompileError> {
+        let token = self.peek();
+
+        Ok(Statement {
+            kind: self.statement_kind()?,
+            line: token.line,
+            column: token.column,
+        })
+    }
+
+    fn statement_kind<T: Word>(&mut self) -> Result<StatementKind<T>, CompileError> {
+        let token = self.peek();
+        let statement = match token.kind {
+            TokenKind::Keyword("let") => {
+                self.advance();
+                let name = self.name()?;
+                self.expect("=")?;
+                StatementKind::Let(name, self.expression()?)
+            }
+            TokenKind::Keyword("read") => {
+                self.advance();
+                StatementKind::Read(self.name()?)
+            }
+            TokenKind::Keyword("print") => {
+                self.advance();
+                StatementKind::Print(self.expression()?)
+            }
+            TokenKind::Keyword("if") => return self.if_statement(),
+            TokenKind::Keyword("while") => {
+                self.advance();
+                let condition = self.expression()?;
+                return Ok(StatementKind::While(condition, self.block()?));
+            }
+            TokenKind::Identifier(_) => {
+                let name = self.name()?;
+                self.expect("=")?;
+                StatementKind::Assign(name, self.expression()?)
+            }
+            ref kind => {
+                return Err(token.error(format!("Expected a statement, but found {}", kind)))
+            }
+        };
+
+        self.expect(";")?;
+        Ok(statement)
+    }
+
+    fn if_statement<T: Word>(&mut self) -> Result<StatementKind<T>, CompileError> {
+        self.expect("if")?;
+        let condition = self.expression()?;
+        let then = self.block()?;
+
+        let otherwise = if !self.eat("else") {
+            Vec::new()
+        } else if self.peek().kind == TokenKind::Keyword("if") {
+            vec![self.statement()?]
+        } else {
+            self.block()?
+        };
+
+        Ok(StatementKind::If(condition, then, otherwise))
+    }
+
+    fn block<T: Word>(&mut self) -> Result<Vec<Statement<T>>, CompileError> {
+        self.expect("{")?;
+
+        let mut statements = Vec::new();
+        while !self.eat("}") {
+            if self.peek().kind == TokenKind::End {
+                self.expect("}")?;
+            }
+            statements.push(self.statement()?);
+        }
+
+        Ok(statements)
+    }
+
+    fn expression<T: Word>(&mut self) -> Result<Expression<T>, CompileError> {
+        let left = self.additive()?;
+
+        let operator = match self.peek().kind {
+            TokenKind::Symbol("<") => Operator::LESSTHAN,
+            TokenKind::Symbol("<=") => Operator::LESSEQUAL,
+            TokenKind::Symbol(">") => Operator::GREATERTHAN,
+            TokenKind::Symbol(">=") => Operator::GREATEREQUAL,
+            TokenKind::Symbol("==") => Operator::EQUALS,
+            TokenKind::Symbol("!=") => Operator::NOTEQUALS,
+            _ => return Ok(left),
+        };
+        self.advance();
+
+        let right = self.additive()?;
+        Ok(Expression::Binary(
+            operator,
+            Box::new(left),
+            Box::new(right),
+        ))
+    }
+
+    fn additive<T: Word>(&mut self) -> Result<Expression<T>, CompileError> {
+        let mut left = self.multiplicative()?;
+
+        loop {
+            let operator = match self.peek().kind {
+                TokenKind::Symbol("+") => Operator::ADD,
+                TokenKind::Symbol("-") => Operator::SUBTRACT,
+                _ => return Ok(left),
+            };
+            self.advance();
+
+            let right = self.multiplicative()?;
+            left = Expression::Binary(operator, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn multiplicative<T: Word>(&mut self) -> Result<Expression<T>, CompileError> {
+        let mut left = self.unary()?;
+
+        while self.eat("*") {
+            let right = self.unary()?;
+            left = Expression::Binary(Operator::MULTIPLY, Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn unary<T: Word>(&mut self) -> Result<Expression<T>, CompileError> {
+        if !self.eat("-") {
+            return self.primary();
+        }
+
+        Ok(match self.unary::<T>()? {
+            Expression::Number(value) => Expression::Number(-value),
+            operand => Expression::Negate(Box::new(operand)),
+        })
+    }
+
+    fn primary<T: Word>(&mut self) -> Result<Expression<T>, CompileError> {
+        let token = self.peek();
+
+        match &token.kind {
+            TokenKind::Number(digits) => {
+                self.advance();
+                digits
+                    .parse::<T>()
+                    .map(Expression::Number)
+                    .map_err(|_| token.error(format!("Invalid number {}", digits)))
+            }
+            TokenKind::Identifier(_) => Ok(Expression::Variable(self.name()?)),
+            TokenKind::Symbol("(") => {
+                self.advance();
+                let expression = self.expression()?;
+                self.expect(")")?;
+                Ok(expression)
+            }
+            kind => Err(token.error(format!("Expected an expression, but found {}", kind))),
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use super::{ParamterMode, Word};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+/// Signature of the closure invoked when an extension opcode is executed.
+pub type Handler<T> = dyn Fn(&mut Machine<T>) -> Result<(), String> + Send + Sync;
+
+#[derive(Clone)]
+pub(crate) struct Extension<T> {
+    pub(crate) parameters: usize,
+    pub(crate) handler: Arc<Handler<T>>,
+}
+
+/// Registry of user-defined opcodes consulted when decoding an instruction.
+#[derive(Clone)]
+pub(crate) struct Extensions<T> {
+    opcodes: HashMap<usize, Extension<T>>,
+}
+
+impl<T> Extensions<T> {
+    pub(crate) fn new() -> Extensions<T> {
+        Extensions {
+            opcodes: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.opcodes.is_empty()
+    }
+
+    pub(crate) fn get(&self, opcode: usize) -> Option<&Extension<T>> {
+        self.opcodes.get(&opcode)
+    }
+
+    pub(crate) fn insert(
+        &mut self,
+        opcode: usize,
+        parameters: usize,
+        handler: Arc<Handler<T>>,
+    ) -> Result<(), String> {
+        match opcode {
+            1..=8 | 99 => return Err(format!("Opcode {} is a built-in instruction", opcode)),
+            9..=98 => {}
+            _ => return Err(format!("Opcode {} must be in the range 1..=99", opcode)),
+        }
+
+        match self.opcodes.entry(opcode) {
+            Entry::Occupied(_) => Err(format!("Opcode {} is already registered", opcode)),
+            Entry::Vacant(entry) => {
+                entry.insert(Extension {
+                    parameters,
+                    handler,
+                });
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for Extensions<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(
+                self.opcodes
+                    .iter()
+                    .map(|(opcode, extension)| (opcode, extension.parameters)),
+            )
+            .finish()
+    }
+}
+
+/// View of a running `Program` handed to an extension opcode's handler.
+///
+/// Parameters are indexed from zero, i.e. `parameter(0)` is the cell directly
+/// after the instruction.
+pub struct Machine<'a, T> {
+    pub(crate) memory: &'a mut Vec<T>,
+    pub(crate) input: &'a mut VecDeque<T>,
+    pub(crate) output: &'a mut VecDeque<T>,
+    pub(crate) i_ptr: usize,
+    pub(crate) modes: &'a [ParamterMode],
+    pub(crate) jump: Option<usize>,
+}
+
+impl<'a, T: Word> Machine<'a, T> {
+    /// Address of the instruction being executed.
+    pub fn instruction_pointer(&self) -> usize {
+        self.i_ptr
+    }
+
+    /// Resolves the parameter at `index` according to the instruction's modes.
+    pub fn parameter(&self, index: usize) -> Result<T, String> {
+        let value = self.raw_parameter(index)?;
+
+        match self.modes[index] {
+            ParamterMode::POSITION => self.read(value.to_address()?),
+            ParamterMode::IMMEDIATE => Ok(value),
+        }
+    }
+
+    /// Reads the parameter at `index` as an address, for use as a write target.
+    pub fn address(&self, index: usize) -> Result<usize, String> {
+        self.raw_parameter(index)?.to_address()
+    }
+
+    pub fn read(&self, address: usize) -> Result<T, String> {
+        self.memory
+            .get(address)
+            .copied()
+            .ok_or_else(|| format!("Address {} is out of bounds", address))
+    }
+
+    pub fn write(&mut self, address: usize, value: T) -> Result<(), String> {
+        match self.memory.get_mut(address) {
+            Some(cell) => {
+                *cell = value;
+                Ok(())
+            }
+            None => Err(format!("Address {} is out of bounds", address)),
+        }
+    }
+
+    pub fn pop_input(&mut self) -> Option<T> {
+        self.input.pop_front()
+    }
+
+    /// Queues a value for `Program::pop_output`. Unlike the built-in output
+    /// instruction this never pauses `Program::execute_until_output`.
+    pub fn push_output(&mut self, value: T) {
+        self.output.push_back(value);
+    }
+
+    /// Continues execution at `address` instead of the next instruction.
+    pub fn jump(&mut self, address: usize) {
+        self.jump = Some(address);
+    }
+
+    fn raw_parameter(&self, index: usize) -> Result<T, String> {
+        if index >= self.modes.len() {
+            return Err(format!(
+                "Parameter {} requested, but the instruction only has {}",
+                index,
+                self.modes.len()
+            ));
+        }
+
+        self.read(self.i_ptr + 1 + index)
+    }
+}
+
+#[cfg(test)]
+mod extensions {
+    use crate::{Program, ProgramState};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn debug_print() {
+        let printed = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&printed);
+
+        let mut program = "1101,2,3,8,1050,8,7,99,0".parse::<Program>().unwrap();
+        program
+            .register_extension(50, 2, move |machine| {
+                let (a, b) = (machine.parameter(0)?, machine.parameter(1)?);
+                sink.lock().unwrap().push((a, b));
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(program.execute(), ProgramState::Complete);
+        assert_eq!(*printed.lock().unwrap(), vec![(5, 7)]);
+    }
+
+    #[test]
+    fn assertion() {
+        let mut program = "3,7,1042,7,42,99,0,0".parse::<Program>().unwrap();
+        program
+            .register_extension(42, 2, |machine| {
+                let (actual, expected) = (machine.parameter(0)?, machine.parameter(1)?);
+                if actual == expected {
+                    Ok(())
+                } else {
+                    Err(format!("Assertion failed: {} != {}", actual, expected))
+                }
+            })
+            .unwrap();
+
+        let mut passing = program.clone();
+        passing.push_back_input(42);
+        assert_eq!(passing.execute(), ProgramState::Complete);
+
+        program.push_back_input(41);
+        assert_eq!(
+            program.execute(),
+            ProgramState::Err("Assertion failed: 41 != 42".to_string())
+        );
+    }
+
+    #[test]
+    fn memory_io_and_jump() {
+        // 11: read input into the address given by parameter 0, output its
+        // double and jump to parameter 1.
+        let mut program = "1011,6,5,99,99,99,0".parse::<Program>().unwrap();
+        program
+            .register_extension(11, 2, |machine| {
+                let value = machine.pop_input().ok_or("No input")?;
+                let target = machine.address(0)?;
+                machine.write(target, value)?;
+                machine.push_output(value * 2);
+
+                let address = machine.parameter(1)?;
+                machine.jump(address as usize);
+                Ok(())
+            })
+            .unwrap();
+
+        program.push_back_input(21);
+        assert_eq!(program.execute(), ProgramState::Complete);
+        assert_eq!(program.pop_output(), Some(42));
+    }
+
+    #[test]
+    fn registration_errors() {
+        let mut program = "9,99".parse::<Program>().unwrap();
+
+        assert!(program.register_extension(1, 3, |_| Ok(())).is_err());
+        assert!(program.register_extension(99, 0, |_| Ok(())).is_err());
+        assert!(program.register_extension(100, 0, |_| Ok(())).is_err());
+        assert!(program.register_extension(0, 0, |_| Ok(())).is_err());
+
+        assert!(program.register_extension(9, 0, |_| Ok(())).is_ok());
+        assert!(program
+            .register_extension(9, 0, |_| Err("Replaced".to_string()))
+            .is_err());
+
+        // The rejected handler must not have replaced the original.
+        assert_eq!(program.execute(), ProgramState::Complete);
+    }
+
+    #[test]
+    fn unregistered_opcode() {
+        let mut program = "50,99".parse::<Program>().unwrap();
+        assert_eq!(
+            program.execute(),
+            ProgramState::Err("Unknown opcode 50 received".to_string())
+        );
+    }
+
+    #[test]
+    fn too_many_modes() {
+        let mut program = "1050,0,99".parse::<Program>().unwrap();
+        program.register_extension(50, 1, |_| Ok(())).unwrap();
+
+        assert_eq!(
+            program.execute(),
+            ProgramState::Err("Opcode value 1050 had remaining modes: 1".to_string())
+        );
+    }
+}
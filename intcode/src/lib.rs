@@ -1,5 +1,76 @@
+#![allow(clippy::upper_case_acronyms)]
+
+pub mod compiler;
+pub mod extension;
+pub mod optimize;
+pub mod pipeline;
+pub mod scheduler;
+pub mod search;
+pub mod translate;
+
+mod analysis;
+
+use extension::{Extensions, Machine};
 use std::collections::VecDeque;
-use std::convert::TryFrom;
+use std::convert::{TryFrom, TryInto};
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Div, Mul, Neg, Rem};
+use std::sync::Arc;
+
+/// The integer type stored in a `Program`'s memory cells.
+///
+/// Implemented for the primitive signed integers from `i16` up, so small
+/// programs can keep compact `i32` memory while big-number puzzles use `i64`
+/// or `i128`.
+pub trait Word:
+    Copy
+    + Debug
+    + Display
+    + Ord
+    + From<u8>
+    + TryInto<usize>
+    + TryFrom<usize>
+    + std::str::FromStr<Err = std::num::ParseIntError>
+    + Add<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Converts the word into a memory address, failing if it is negative or
+    /// does not fit into a `usize`.
+    fn to_address(self) -> Result<usize, String> {
+        self.try_into()
+            .map_err(|_| format!("{} is not a valid address", self))
+    }
+
+    /// Converts a memory address into a word, failing if it does not fit.
+    fn from_address(address: usize) -> Result<Self, String> {
+        Self::try_from(address).map_err(|_| format!("Address {} does not fit into a word", address))
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_word {
+    ($($word:ty),*) => {
+        $(
+            impl Word for $word {
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$word>::checked_add(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$word>::checked_mul(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_word!(i16, i32, i64, i128, isize);
 
 #[derive(Debug)]
 enum OpCode {
@@ -12,6 +83,7 @@ enum OpCode {
     FJUMP(ParamterMode, ParamterMode),
     LESSTHAN(ParamterMode, ParamterMode),
     EQUALS(ParamterMode, ParamterMode),
+    EXTENSION(usize, Vec<ParamterMode>),
 }
 
 #[derive(Debug)]
@@ -20,313 +92,434 @@ enum ParamterMode {
     IMMEDIATE,
 }
 
-impl TryFrom<i32> for ParamterMode {
-    type Error = String;
-
-    fn try_from(value: i32) -> Result<Self, Self::Error> {
-        match value {
-            0 => Ok(ParamterMode::POSITION),
-            1 => Ok(ParamterMode::IMMEDIATE),
-            _ => Err(format!("Unknown paramter mode value {}", value)),
+impl ParamterMode {
+    fn decode<T: Word>(value: T) -> Result<Self, String> {
+        if value == T::from(0) {
+            Ok(ParamterMode::POSITION)
+        } else if value == T::from(1) {
+            Ok(ParamterMode::IMMEDIATE)
+        } else {
+            Err(format!("Unknown paramter mode value {}", value))
         }
     }
 }
 
-impl TryFrom<i32> for OpCode {
-    type Error = String;
-
-    fn try_from(value: i32) -> Result<Self, Self::Error> {
-        if value < 0 {
+impl OpCode {
+    /// Decodes an instruction, consulting `extensions` for any opcode that is
+    /// not built in.
+    fn decode<T: Word>(value: T, extensions: &Extensions<T>) -> Result<Self, String> {
+        if value < T::from(0) {
             return Err(format!("Opcode cannot be negative, but received {}", value));
         }
 
         let div_mod =
-            |dividend, divisor| -> (i32, i32) { (dividend / divisor, dividend % divisor) };
+            |dividend: T, divisor: T| -> (T, T) { (dividend / divisor, dividend % divisor) };
+        let next_mode = |modes: T| -> Result<(T, ParamterMode), String> {
+            let (modes, mode) = div_mod(modes, T::from(10));
+            Ok((modes, ParamterMode::decode(mode)?))
+        };
 
-        let (modes, opcode_num) = div_mod(value, 100);
+        let (modes, opcode_num) = div_mod(value, T::from(100));
+        let opcode_num: usize = opcode_num
+            .try_into()
+            .map_err(|_| format!("Unknown opcode {} received", value))?;
 
         let (opcode, modes_remainder) = match opcode_num {
             1 => {
-                let (modes, mode_1) = div_mod(modes, 10);
-                let (modes, mode_2) = div_mod(modes, 10);
-
-                (
-                    OpCode::ADD(
-                        ParamterMode::try_from(mode_1)?,
-                        ParamterMode::try_from(mode_2)?,
-                    ),
-                    modes,
-                )
+                let (modes, mode_1) = next_mode(modes)?;
+                let (modes, mode_2) = next_mode(modes)?;
+
+                (OpCode::ADD(mode_1, mode_2), modes)
             }
             2 => {
-                let (modes, mode_1) = div_mod(modes, 10);
-                let (modes, mode_2) = div_mod(modes, 10);
-
-                (
-                    OpCode::MULTIPLY(
-                        ParamterMode::try_from(mode_1)?,
-                        ParamterMode::try_from(mode_2)?,
-                    ),
-                    modes,
-                )
+                let (modes, mode_1) = next_mode(modes)?;
+                let (modes, mode_2) = next_mode(modes)?;
+
+                (OpCode::MULTIPLY(mode_1, mode_2), modes)
             }
             3 => (OpCode::INPUT, modes),
             4 => {
-                let (modes, mode_1) = div_mod(modes, 10);
+                let (modes, mode_1) = next_mode(modes)?;
 
-                (OpCode::OUTPUT(ParamterMode::try_from(mode_1)?), modes)
+                (OpCode::OUTPUT(mode_1), modes)
             }
             5 => {
-                let (modes, mode_1) = div_mod(modes, 10);
-                let (modes, mode_2) = div_mod(modes, 10);
-
-                (
-                    OpCode::TJUMP(
-                        ParamterMode::try_from(mode_1)?,
-                        ParamterMode::try_from(mode_2)?,
-                    ),
-                    modes,
-                )
+                let (modes, mode_1) = next_mode(modes)?;
+                let (modes, mode_2) = next_mode(modes)?;
+
+                (OpCode::TJUMP(mode_1, mode_2), modes)
             }
             6 => {
-                let (modes, mode_1) = div_mod(modes, 10);
-                let (modes, mode_2) = div_mod(modes, 10);
-
-                (
-                    OpCode::FJUMP(
-                        ParamterMode::try_from(mode_1)?,
-                        ParamterMode::try_from(mode_2)?,
-                    ),
-                    modes,
-                )
+                let (modes, mode_1) = next_mode(modes)?;
+                let (modes, mode_2) = next_mode(modes)?;
+
+                (OpCode::FJUMP(mode_1, mode_2), modes)
             }
             7 => {
-                let (modes, mode_1) = div_mod(modes, 10);
-                let (modes, mode_2) = div_mod(modes, 10);
-
-                (
-                    OpCode::LESSTHAN(
-                        ParamterMode::try_from(mode_1)?,
-                        ParamterMode::try_from(mode_2)?,
-                    ),
-                    modes,
-                )
+                let (modes, mode_1) = next_mode(modes)?;
+                let (modes, mode_2) = next_mode(modes)?;
+
+                (OpCode::LESSTHAN(mode_1, mode_2), modes)
             }
             8 => {
-                let (modes, mode_1) = div_mod(modes, 10);
-                let (modes, mode_2) = div_mod(modes, 10);
-
-                (
-                    OpCode::EQUALS(
-                        ParamterMode::try_from(mode_1)?,
-                        ParamterMode::try_from(mode_2)?,
-                    ),
-                    modes,
-                )
+                let (modes, mode_1) = next_mode(modes)?;
+                let (modes, mode_2) = next_mode(modes)?;
+
+                (OpCode::EQUALS(mode_1, mode_2), modes)
             }
             99 => (OpCode::EXIT, modes),
-            _ => return Err(format!("Unknown opcode {} received", value)),
+            _ => match extensions.get(opcode_num) {
+                Some(extension) => {
+                    let mut modes = modes;
+                    let mut parameter_modes = Vec::with_capacity(extension.parameters);
+                    for _ in 0..extension.parameters {
+                        let (remainder, mode) = next_mode(modes)?;
+                        modes = remainder;
+                        parameter_modes.push(mode);
+                    }
+
+                    (OpCode::EXTENSION(opcode_num, parameter_modes), modes)
+                }
+                None => return Err(format!("Unknown opcode {} received", value)),
+            },
         };
 
-        match modes_remainder {
-            0 => Ok(opcode),
-            _ => Err(format!(
+        if modes_remainder == T::from(0) {
+            Ok(opcode)
+        } else {
+            Err(format!(
                 "Opcode value {} had remaining modes: {}",
-                value, modes
-            )),
+                value, modes_remainder
+            ))
         }
     }
 }
 
 #[derive(Clone, Debug)]
-pub struct Program {
-    int_codes: Vec<i32>,
-    input: VecDeque<i32>,
-    output: VecDeque<i32>,
+pub struct Program<T = i32> {
+    int_codes: Vec<T>,
+    input: VecDeque<T>,
+    output: VecDeque<T>,
     i_ptr: usize,
+    extensions: Extensions<T>,
 }
 
-#[derive(PartialEq)]
-pub enum ProgramState {
+#[derive(Clone, PartialEq, Debug)]
+pub enum ProgramState<T = i32> {
     RequiresInput,
+    Output(T),
     Complete,
     Err(String),
 }
 
-impl Program {
-    pub fn push_back_input(&mut self, input: i32) {
+impl<T: Word> Program<T> {
+    pub fn new(int_codes: Vec<T>) -> Program<T> {
+        Program {
+            int_codes,
+            input: VecDeque::new(),
+            output: VecDeque::new(),
+            i_ptr: 0,
+            extensions: Extensions::new(),
+        }
+    }
+
+    pub fn push_back_input(&mut self, input: T) {
         self.input.push_back(input);
     }
 
-    pub fn set_noun(&mut self, noun: i32) {
+    pub fn set_noun(&mut self, noun: T) {
         self.int_codes[1] = noun;
     }
 
-    pub fn set_verb(&mut self, verb: i32) {
+    pub fn set_verb(&mut self, verb: T) {
         self.int_codes[2] = verb;
     }
 
-    pub fn get_zero(&self) -> i32 {
+    pub fn get_zero(&self) -> T {
         self.int_codes[0]
     }
 
-    pub fn pop_output(&mut self) -> Option<i32> {
+    pub fn pop_output(&mut self) -> Option<T> {
         self.output.pop_front()
     }
 
-    pub fn pop_latest_output(&mut self) -> Option<i32> {
+    pub fn pop_latest_output(&mut self) -> Option<T> {
         self.output.pop_back()
     }
 
-    pub fn execute(&mut self) -> ProgramState {
+    /// Registers a user-defined instruction taking `parameters` parameters,
+    /// whose modes are decoded like those of the built-in instructions.
+    ///
+    /// Once `handler` returns the program continues after the instruction,
+    /// unless the handler jumped elsewhere. Errors halt the program with
+    /// `ProgramState::Err`.
+    pub fn register_extension<F>(
+        &mut self,
+        opcode: usize,
+        parameters: usize,
+        handler: F,
+    ) -> Result<(), String>
+    where
+        F: Fn(&mut Machine<T>) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.extensions
+            .insert(opcode, parameters, Arc::new(handler))
+    }
+
+    /// Executes until the program completes, fails or runs out of input.
+    /// Outputs are queued for `pop_output`.
+    pub fn execute(&mut self) -> ProgramState<T> {
+        self.run(false)
+    }
+
+    /// Executes until the program emits a value, returning it as
+    /// `ProgramState::Output` instead of queueing it for `pop_output`.
+    pub fn execute_until_output(&mut self) -> ProgramState<T> {
+        self.run(true)
+    }
+
+    /// Returns an iterator over the program's outputs, starting with any that
+    /// are already queued and executing further as values are requested.
+    pub fn outputs(&mut self) -> Outputs<'_, T> {
+        Outputs {
+            program: self,
+            state: None,
+        }
+    }
+
+    fn run(&mut self, pause_on_output: bool) -> ProgramState<T> {
         loop {
-            if self.i_ptr >= self.int_codes.len() {
-                return ProgramState::Err("Instruction pointer out of bounds".to_string());
+            match self.step(pause_on_output) {
+                Ok(None) => {}
+                Ok(Some(state)) => return state,
+                Err(err) => return ProgramState::Err(err),
             }
+        }
+    }
 
-            match OpCode::try_from(self.int_codes[self.i_ptr]).unwrap() {
-                OpCode::ADD(mode_1, mode_2) => {
-                    let x = match mode_1 {
-                        ParamterMode::POSITION => self.int_codes[self.int_codes[self.i_ptr + 1] as usize],
-                        ParamterMode::IMMEDIATE => self.int_codes[self.i_ptr + 1],
-                    };
-                    let y = match mode_2 {
-                        ParamterMode::POSITION => self.int_codes[self.int_codes[self.i_ptr + 2] as usize],
-                        ParamterMode::IMMEDIATE => self.int_codes[self.i_ptr + 2],
-                    };
-                    let target = self.int_codes[self.i_ptr + 3] as usize;
-
-                    self.int_codes[target as usize] = x + y;
-                    self.i_ptr += 4;
-                }
-                OpCode::MULTIPLY(mode_1, mode_2) => {
-                    let x = match mode_1 {
-                        ParamterMode::POSITION => self.int_codes[self.int_codes[self.i_ptr + 1] as usize],
-                        ParamterMode::IMMEDIATE => self.int_codes[self.i_ptr + 1],
-                    };
-                    let y = match mode_2 {
-                        ParamterMode::POSITION => self.int_codes[self.int_codes[self.i_ptr + 2] as usize],
-                        ParamterMode::IMMEDIATE => self.int_codes[self.i_ptr + 2],
-                    };
-                    let target = self.int_codes[self.i_ptr + 3] as usize;
-
-                    self.int_codes[target as usize] = x * y;
-                    self.i_ptr += 4;
-                }
-                OpCode::INPUT => {
-                    let target = self.int_codes[self.i_ptr + 1] as usize;
-                    match self.input.pop_front() {
-                        Some(v) => self.int_codes[target] = v,
-                        None => return ProgramState::RequiresInput,
-                    }
-                    self.i_ptr += 2;
-                }
-                OpCode::OUTPUT(mode_1) => {
-                    let value = match mode_1 {
-                        ParamterMode::POSITION => self.int_codes[self.int_codes[self.i_ptr + 1] as usize],
-                        ParamterMode::IMMEDIATE => self.int_codes[self.i_ptr + 1],
-                    };
+    /// Executes a single instruction, returning the state the program halted
+    /// in or `None` if execution can continue.
+    fn step(&mut self, pause_on_output: bool) -> Result<Option<ProgramState<T>>, String> {
+        if self.i_ptr >= self.int_codes.len() {
+            return Err("Instruction pointer out of bounds".to_string());
+        }
 
-                    self.output.push_back(value);
+        match OpCode::decode(self.int_codes[self.i_ptr], &self.extensions)? {
+            OpCode::ADD(mode_1, mode_2) => {
+                let x = self.parameter(1, &mode_1)?;
+                let y = self.parameter(2, &mode_2)?;
+
+                let sum = x
+                    .checked_add(y)
+                    .ok_or_else(|| format!("Overflow adding {} and {}", x, y))?;
+                self.write(3, sum)?;
+                self.i_ptr += 4;
+            }
+            OpCode::MULTIPLY(mode_1, mode_2) => {
+                let x = self.parameter(1, &mode_1)?;
+                let y = self.parameter(2, &mode_2)?;
+
+                let product = x
+                    .checked_mul(y)
+                    .ok_or_else(|| format!("Overflow multiplying {} and {}", x, y))?;
+                self.write(3, product)?;
+                self.i_ptr += 4;
+            }
+            OpCode::INPUT => match self.input.pop_front() {
+                Some(value) => {
+                    self.write(1, value)?;
                     self.i_ptr += 2;
                 }
-                OpCode::TJUMP(mode_1, mode_2) => {
-                    let x = match mode_1 {
-                        ParamterMode::POSITION => self.int_codes[self.int_codes[self.i_ptr + 1] as usize],
-                        ParamterMode::IMMEDIATE => self.int_codes[self.i_ptr + 1],
-                    };
-                    let y = match mode_2 {
-                        ParamterMode::POSITION => self.int_codes[self.int_codes[self.i_ptr + 2] as usize],
-                        ParamterMode::IMMEDIATE => self.int_codes[self.i_ptr + 2],
-                    };
-
-                    if x != 0 {
-                        self.i_ptr = usize::try_from(y).unwrap();
-                    } else {
-                        self.i_ptr += 3;
-                    }
+                None => return Ok(Some(ProgramState::RequiresInput)),
+            },
+            OpCode::OUTPUT(mode_1) => {
+                let value = self.parameter(1, &mode_1)?;
+                self.i_ptr += 2;
+
+                if pause_on_output {
+                    return Ok(Some(ProgramState::Output(value)));
                 }
-
-                OpCode::FJUMP(mode_1, mode_2) => {
-                    let x = match mode_1 {
-                        ParamterMode::POSITION => self.int_codes[self.int_codes[self.i_ptr + 1] as usize],
-                        ParamterMode::IMMEDIATE => self.int_codes[self.i_ptr + 1],
-                    };
-                    let y = match mode_2 {
-                        ParamterMode::POSITION => self.int_codes[self.int_codes[self.i_ptr + 2] as usize],
-                        ParamterMode::IMMEDIATE => self.int_codes[self.i_ptr + 2],
-                    };
-
-                    if x == 0 {
-                        self.i_ptr = usize::try_from(y).unwrap();
-                    } else {
-                        self.i_ptr += 3;
-                    }
+                self.output.push_back(value);
+            }
+            OpCode::TJUMP(mode_1, mode_2) => {
+                let x = self.parameter(1, &mode_1)?;
+                let y = self.parameter(2, &mode_2)?;
+
+                if x != T::from(0) {
+                    self.i_ptr = y.to_address()?;
+                } else {
+                    self.i_ptr += 3;
                 }
+            }
+            OpCode::FJUMP(mode_1, mode_2) => {
+                let x = self.parameter(1, &mode_1)?;
+                let y = self.parameter(2, &mode_2)?;
+
+                if x == T::from(0) {
+                    self.i_ptr = y.to_address()?;
+                } else {
+                    self.i_ptr += 3;
+                }
+            }
+            OpCode::LESSTHAN(mode_1, mode_2) => {
+                let x = self.parameter(1, &mode_1)?;
+                let y = self.parameter(2, &mode_2)?;
 
-                OpCode::LESSTHAN(mode_1, mode_2) => {
-                    let x = match mode_1 {
-                        ParamterMode::POSITION => self.int_codes[self.int_codes[self.i_ptr + 1] as usize],
-                        ParamterMode::IMMEDIATE => self.int_codes[self.i_ptr + 1],
-                    };
-                    let y = match mode_2 {
-                        ParamterMode::POSITION => self.int_codes[self.int_codes[self.i_ptr + 2] as usize],
-                        ParamterMode::IMMEDIATE => self.int_codes[self.i_ptr + 2],
-                    };
-                    let target = self.int_codes[self.i_ptr + 3] as usize;
-
-                    if x < y {
-                        self.int_codes[target] = 1;
-                    } else {
-                        self.int_codes[target] = 0;
-                    }
+                self.write(3, T::from((x < y) as u8))?;
+                self.i_ptr += 4;
+            }
+            OpCode::EQUALS(mode_1, mode_2) => {
+                let x = self.parameter(1, &mode_1)?;
+                let y = self.parameter(2, &mode_2)?;
 
-                    self.i_ptr += 4;
-                }
+                self.write(3, T::from((x == y) as u8))?;
+                self.i_ptr += 4;
+            }
+            OpCode::EXIT => return Ok(Some(ProgramState::Complete)),
+            OpCode::EXTENSION(opcode, modes) => {
+                let handler = match self.extensions.get(opcode) {
+                    Some(extension) => Arc::clone(&extension.handler),
+                    None => return Err(format!("Unknown opcode {} received", opcode)),
+                };
+
+                let mut machine = Machine {
+                    memory: &mut self.int_codes,
+                    input: &mut self.input,
+                    output: &mut self.output,
+                    i_ptr: self.i_ptr,
+                    modes: &modes,
+                    jump: None,
+                };
+                handler(&mut machine)?;
+
+                self.i_ptr = match machine.jump {
+                    Some(address) => address,
+                    None => self.i_ptr + modes.len() + 1,
+                };
+            }
+        }
 
-                OpCode::EQUALS(mode_1, mode_2) => {
-                    let x = match mode_1 {
-                        ParamterMode::POSITION => self.int_codes[self.int_codes[self.i_ptr + 1] as usize],
-                        ParamterMode::IMMEDIATE => self.int_codes[self.i_ptr + 1],
-                    };
-                    let y = match mode_2 {
-                        ParamterMode::POSITION => self.int_codes[self.int_codes[self.i_ptr + 2] as usize],
-                        ParamterMode::IMMEDIATE => self.int_codes[self.i_ptr + 2],
-                    };
-                    let target = self.int_codes[self.i_ptr + 3] as usize;
-
-                    if x == y {
-                        self.int_codes[target] = 1;
-                    } else {
-                        self.int_codes[target] = 0;
-                    }
+        Ok(None)
+    }
 
-                    self.i_ptr += 4;
-                }
+    fn read(&self, address: usize) -> Result<T, String> {
+        self.int_codes
+            .get(address)
+            .copied()
+            .ok_or_else(|| format!("Address {} is out of bounds", address))
+    }
 
-                OpCode::EXIT => break,
+    /// Resolves the instruction parameter at `offset` according to its mode.
+    fn parameter(&self, offset: usize, mode: &ParamterMode) -> Result<T, String> {
+        let value = self.read(self.i_ptr + offset)?;
+
+        match mode {
+            ParamterMode::POSITION => self.read(value.to_address()?),
+            ParamterMode::IMMEDIATE => Ok(value),
+        }
+    }
+
+    /// Writes `value` to the address held by the instruction parameter at `offset`.
+    fn write(&mut self, offset: usize, value: T) -> Result<(), String> {
+        let target = self.read(self.i_ptr + offset)?.to_address()?;
+
+        match self.int_codes.get_mut(target) {
+            Some(cell) => {
+                *cell = value;
+                Ok(())
+            }
+            None => Err(format!("Address {} is out of bounds", target)),
+        }
+    }
+}
+
+/// Iterator over a `Program`'s outputs, created by `Program::outputs`.
+///
+/// Iteration ends once the program stops for any reason other than producing
+/// a value; that final state is then available from `Outputs::state`.
+pub struct Outputs<'a, T> {
+    program: &'a mut Program<T>,
+    state: Option<ProgramState<T>>,
+}
+
+impl<'a, T: Word> Outputs<'a, T> {
+    /// The state the program stopped in, or `None` while it is still running.
+    pub fn state(&self) -> Option<&ProgramState<T>> {
+        self.state.as_ref()
+    }
+}
+
+impl<'a, T: Word> Iterator for Outputs<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(value) = self.program.pop_output() {
+            return Some(value);
+        }
+
+        if self.state.is_some() {
+            return None;
+        }
+
+        match self.program.execute_until_output() {
+            ProgramState::Output(value) => Some(value),
+            state => {
+                self.state = Some(state);
+                None
             }
         }
+    }
+}
 
-        ProgramState::Complete
+/// Why a program's text is not a list of comma separated integers,
+/// pointing at the first invalid code. Lines and columns count from 1, and
+/// columns count characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseProgramError {
+    pub line: usize,
+    pub column: usize,
+    /// The invalid code, empty if it is missing.
+    pub code: String,
+    pub error: std::num::ParseIntError,
+}
+
+impl std::fmt::Display for ParseProgramError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: {} at {:?}",
+            self.line, self.column, self.error, self.code
+        )
     }
 }
 
-impl std::str::FromStr for Program {
-    type Err = std::num::ParseIntError;
+impl std::error::Error for ParseProgramError {}
+
+impl<T: Word> std::str::FromStr for Program<T> {
+    type Err = ParseProgramError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let int_codes = s
             .trim()
             .split(',')
-            .map(|num_str| num_str.parse::<i32>())
-            .collect::<Result<Vec<i32>, Self::Err>>()?;
-        Ok(Program {
-            int_codes,
-            input: VecDeque::new(),
-            output: VecDeque::new(),
-            i_ptr: 0,
-        })
+            .map(|code| {
+                let code = code.trim();
+                code.parse::<T>().map_err(|error| {
+                    // Every code is a slice of `s`, so its offset is the
+                    // distance between their addresses.
+                    let before = &s[..code.as_ptr() as usize - s.as_ptr() as usize];
+                    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+
+                    ParseProgramError {
+                        line: before.matches('\n').count() + 1,
+                        column: before[line_start..].chars().count() + 1,
+                        code: code.to_string(),
+                        error,
+                    }
+                })
+            })
+            .collect::<Result<Vec<T>, Self::Err>>()?;
+        Ok(Program::new(int_codes))
     }
 }
 
@@ -446,4 +639,121 @@ mod int_code {
         test_program_inout(code, 8, 1000);
         test_program_inout(code, 9, 1001);
     }
+
+    #[test]
+    fn wide_words() {
+        let mut program = "1102,34915192,34915192,7,4,7,99,0"
+            .parse::<Program<i64>>()
+            .unwrap();
+        assert_eq!(program.execute(), ProgramState::Complete);
+        assert_eq!(program.pop_output().unwrap(), 1_219_070_632_396_864);
+
+        let mut program = "104,1125899906842624000000,99"
+            .parse::<Program<i128>>()
+            .unwrap();
+        assert_eq!(program.execute(), ProgramState::Complete);
+        assert_eq!(program.pop_output().unwrap(), 1_125_899_906_842_624_000_000);
+    }
+
+    #[test]
+    fn overflow() {
+        let mut program = "1101,2147483647,1,5,99,0".parse::<Program>().unwrap();
+        assert_eq!(
+            program.execute(),
+            ProgramState::Err("Overflow adding 2147483647 and 1".to_string())
+        );
+
+        let mut program = "1102,65536,65536,5,99,0".parse::<Program>().unwrap();
+        assert_eq!(
+            program.execute(),
+            ProgramState::Err("Overflow multiplying 65536 and 65536".to_string())
+        );
+
+        let mut program = "1102,65536,65536,7,4,7,99,0"
+            .parse::<Program<i64>>()
+            .unwrap();
+        assert_eq!(program.execute(), ProgramState::Complete);
+        assert_eq!(program.pop_output(), Some(1 << 32));
+    }
+
+    #[test]
+    fn word_too_wide() {
+        assert!("104,1125899906842624,99".parse::<Program>().is_err());
+        assert!("104,1125899906842624,99".parse::<Program<i64>>().is_ok());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(" 1, 0,\n0,0,99\n".parse::<Program>().is_ok());
+
+        let err = "104,1125899906842624,99".parse::<Program>().unwrap_err();
+        assert_eq!((err.line, err.column), (1, 5));
+        assert_eq!(
+            err.to_string(),
+            "1:5: number too large to fit in target type at \"1125899906842624\""
+        );
+
+        let err = "1,0,\n0,,99".parse::<Program>().unwrap_err();
+        assert_eq!((err.line, err.column, err.code.as_str()), (2, 3, ""));
+    }
+
+    #[test]
+    fn invalid_address() {
+        let mut program = "4,-1,99".parse::<Program>().unwrap();
+        assert_eq!(
+            program.execute(),
+            ProgramState::Err("-1 is not a valid address".to_string())
+        );
+
+        let mut program = "4,100,99".parse::<Program>().unwrap();
+        assert_eq!(
+            program.execute(),
+            ProgramState::Err("Address 100 is out of bounds".to_string())
+        );
+    }
+
+    #[test]
+    fn until_output() {
+        let mut program = "104,1,104,2,3,0,99".parse::<Program>().unwrap();
+
+        assert_eq!(program.execute_until_output(), ProgramState::Output(1));
+        assert_eq!(program.execute_until_output(), ProgramState::Output(2));
+        assert_eq!(program.execute_until_output(), ProgramState::RequiresInput);
+        assert_eq!(program.pop_output(), None);
+
+        program.push_back_input(5);
+        assert_eq!(program.execute_until_output(), ProgramState::Complete);
+    }
+
+    #[test]
+    fn outputs_iterator() {
+        let mut program = "104,1,3,0,4,0,99".parse::<Program>().unwrap();
+
+        let mut outputs = program.outputs();
+        assert_eq!(outputs.by_ref().collect::<Vec<i32>>(), vec![1]);
+        assert_eq!(outputs.state(), Some(&ProgramState::RequiresInput));
+
+        program.push_back_input(7);
+        let mut outputs = program.outputs();
+        assert_eq!(outputs.by_ref().collect::<Vec<i32>>(), vec![7]);
+        assert_eq!(outputs.state(), Some(&ProgramState::Complete));
+    }
+
+    #[test]
+    fn outputs_drains_queue_first() {
+        let mut program = "104,1,3,0,104,2,99".parse::<Program>().unwrap();
+        assert_eq!(program.execute(), ProgramState::RequiresInput);
+
+        program.push_back_input(0);
+        assert_eq!(program.outputs().collect::<Vec<i32>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn invalid_opcode() {
+        let mut program = "42,99".parse::<Program<i128>>().unwrap();
+        assert_eq!(
+            program.execute(),
+            ProgramState::Err("Unknown opcode 42 received".to_string())
+        );
+    }
 }
@@ -0,0 +1,403 @@
+//! Peephole optimisation of intcode programs.
+//!
+//! Instructions are rewritten in place, so no addresses move. A rewrite is
+//! only made when none of the instruction's cells are ever read as data or
+//! written to, which requires every instruction the program can execute to be
+//! known up front. Programs with computed jumps, self-modifying code or
+//! extension opcodes are therefore rejected.
+
+use super::analysis::{reachable, Instruction, Target};
+use super::{OpCode, ParamterMode, Program, Word};
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Idiom {
+    /// `ADD 0, x`, rewritten to the canonical copy `ADD x, 0`.
+    Copy,
+    /// Arithmetic or a comparison whose result is known, rewritten to store
+    /// the constant directly.
+    ConstantFold,
+    /// A jump to a jump, rewritten to go straight to the final target.
+    JumpThreading,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Rewrite<T> {
+    pub address: usize,
+    pub idiom: Idiom,
+    pub before: Vec<T>,
+    pub after: Vec<T>,
+}
+
+/// An optimised program together with the rewrites that produced it.
+pub struct Optimization<T> {
+    pub program: Program<T>,
+    pub rewrites: Vec<Rewrite<T>>,
+}
+
+impl std::fmt::Display for Idiom {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Idiom::Copy => write!(f, "copy"),
+            Idiom::ConstantFold => write!(f, "constant fold"),
+            Idiom::JumpThreading => write!(f, "jump threading"),
+        }
+    }
+}
+
+impl<T: Word> std::fmt::Display for Rewrite<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |cells: &[T]| {
+            cells
+                .iter()
+                .map(T::to_string)
+                .collect::<Vec<String>>()
+                .join(",")
+        };
+
+        write!(
+            f,
+            "{}: {} -> {} ({})",
+            self.address,
+            join(&self.before),
+            join(&self.after),
+            self.idiom
+        )
+    }
+}
+
+pub fn optimize<T: Word>(program: &Program<T>) -> Result<Optimization<T>, String> {
+    if !program.extensions.is_empty() {
+        return Err("Programs with extension opcodes cannot be optimized".to_string());
+    }
+
+    let memory = &program.int_codes;
+    let (instructions, computed) = reachable(memory, vec![0, program.i_ptr]);
+    if computed {
+        return Err("Programs with computed jumps cannot be optimized".to_string());
+    }
+
+    let mut owners = BTreeMap::new();
+    let mut overlapping = HashSet::new();
+    for instruction in instructions.values() {
+        for cell in instruction.cells() {
+            if let Some(owner) = owners.insert(cell, instruction.address) {
+                overlapping.insert(owner);
+                overlapping.insert(instruction.address);
+            }
+        }
+    }
+
+    let written = instructions
+        .values()
+        .filter_map(Instruction::write_target)
+        .collect::<HashSet<usize>>();
+    if let Some(cell) = written.iter().find(|cell| owners.contains_key(cell)) {
+        return Err(format!(
+            "Self-modifying programs cannot be optimized, instruction cell {} is written",
+            cell
+        ));
+    }
+
+    let read = instructions
+        .values()
+        .flat_map(Instruction::reads)
+        .collect::<HashSet<usize>>();
+
+    let mut int_codes = memory.clone();
+    let mut rewrites = Vec::new();
+
+    for instruction in instructions.values() {
+        let untouched = !overlapping.contains(&instruction.address)
+            && instruction.cells().all(|cell| !read.contains(&cell));
+        if !untouched {
+            continue;
+        }
+
+        let rewrite = match instruction.opcode {
+            OpCode::TJUMP(..) | OpCode::FJUMP(..) => thread_jump(instruction, &instructions),
+            _ => rewrite_arithmetic(instruction, memory.len()),
+        };
+
+        if let Some((idiom, after)) = rewrite {
+            let before = memory[instruction.cells()].to_vec();
+            if before != after {
+                int_codes[instruction.cells()].copy_from_slice(&after);
+                rewrites.push(Rewrite {
+                    address: instruction.address,
+                    idiom,
+                    before,
+                    after,
+                });
+            }
+        }
+    }
+
+    Ok(Optimization {
+        program: Program {
+            int_codes,
+            ..program.clone()
+        },
+        rewrites,
+    })
+}
+
+/// Runs `original` and `optimized` on each set of inputs, failing on the first
+/// difference in their outputs or final state.
+pub fn verify<T: Word>(
+    original: &Program<T>,
+    optimized: &Program<T>,
+    inputs: &[Vec<T>],
+) -> Result<(), String> {
+    let run = |program: &Program<T>, input: &[T]| {
+        let mut program = program.clone();
+        input
+            .iter()
+            .for_each(|&value| program.push_back_input(value));
+
+        let state = program.execute();
+        let outputs = std::iter::from_fn(|| program.pop_output()).collect::<Vec<T>>();
+        (state, outputs)
+    };
+
+    for input in inputs {
+        let expected = run(original, input);
+        let actual = run(optimized, input);
+
+        if expected != actual {
+            return Err(format!(
+                "Input {:?} produced {:?}, but the unoptimized program produced {:?}",
+                input, actual, expected
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Encodes an opcode number and its parameter modes as an instruction value.
+fn encode<T: Word>(opcode: u8, modes: &[&ParamterMode]) -> T {
+    let ten = T::from(10);
+    let mut value = T::from(0);
+
+    for mode in modes.iter().rev() {
+        let digit = match mode {
+            ParamterMode::POSITION => 0,
+            ParamterMode::IMMEDIATE => 1,
+        };
+        value = value * ten + T::from(digit);
+    }
+
+    value * T::from(100) + T::from(opcode)
+}
+
+/// Rewrites arithmetic whose result is known or which is a copy. Operands
+/// are only dropped when reading them cannot fail, i.e. they are immediate
+/// or in the `len` cells of memory.
+fn rewrite_arithmetic<T: Word>(
+    instruction: &Instruction<T>,
+    len: usize,
+) -> Option<(Idiom, Vec<T>)> {
+    let (x, y, target) = match instruction.parameters[..] {
+        [x, y, target] => (x, y, target),
+        _ => return None,
+    };
+    let zero = T::from(0);
+    let readable = |mode: &ParamterMode, parameter: T| match mode {
+        ParamterMode::IMMEDIATE => true,
+        ParamterMode::POSITION => parameter.to_address().is_ok_and(|address| address < len),
+    };
+
+    let constant = |value: T| -> Vec<T> {
+        let immediate = &ParamterMode::IMMEDIATE;
+        vec![encode(1, &[immediate, immediate]), value, zero, target]
+    };
+    let copy = |mode: &ParamterMode, value: T| -> Vec<T> {
+        vec![
+            encode(1, &[mode, &ParamterMode::IMMEDIATE]),
+            value,
+            zero,
+            target,
+        ]
+    };
+
+    use ParamterMode::IMMEDIATE;
+    match &instruction.opcode {
+        OpCode::ADD(IMMEDIATE, IMMEDIATE) => {
+            Some((Idiom::ConstantFold, constant(x.checked_add(y)?)))
+        }
+        OpCode::MULTIPLY(IMMEDIATE, IMMEDIATE) => {
+            Some((Idiom::ConstantFold, constant(x.checked_mul(y)?)))
+        }
+        OpCode::LESSTHAN(IMMEDIATE, IMMEDIATE) => {
+            Some((Idiom::ConstantFold, constant(T::from((x < y) as u8))))
+        }
+        OpCode::EQUALS(IMMEDIATE, IMMEDIATE) => {
+            Some((Idiom::ConstantFold, constant(T::from((x == y) as u8))))
+        }
+        OpCode::ADD(mode, IMMEDIATE) if y == zero => Some((Idiom::Copy, copy(mode, x))),
+        OpCode::ADD(IMMEDIATE, mode) if x == zero => Some((Idiom::Copy, copy(mode, y))),
+        OpCode::MULTIPLY(mode, IMMEDIATE) if y == zero && readable(mode, x) => {
+            Some((Idiom::ConstantFold, constant(zero)))
+        }
+        OpCode::MULTIPLY(IMMEDIATE, mode) if x == zero && readable(mode, y) => {
+            Some((Idiom::ConstantFold, constant(zero)))
+        }
+        _ => None,
+    }
+}
+
+/// Follows the static target of a jump through unconditional jumps, and jumps
+/// that are never taken, to the first instruction that does real work.
+fn thread_jump<T: Word>(
+    instruction: &Instruction<T>,
+    instructions: &BTreeMap<usize, Instruction<T>>,
+) -> Option<(Idiom, Vec<T>)> {
+    let opcode = match instruction.opcode {
+        OpCode::TJUMP(..) => 5,
+        _ => 6,
+    };
+    let mut target = match instruction.flow() {
+        (_, Some(Target::Static(target))) => target,
+        _ => return None,
+    };
+
+    let mut visited = HashSet::new();
+    while visited.insert(target) {
+        let next = match instructions.get(&target) {
+            Some(next) if matches!(next.opcode, OpCode::TJUMP(..) | OpCode::FJUMP(..)) => next,
+            _ => break,
+        };
+
+        target = match next.flow() {
+            (false, Some(Target::Static(target))) => target,
+            (true, None) => next.next(),
+            _ => break,
+        };
+    }
+
+    let after = vec![
+        encode(opcode, &instruction.modes()),
+        instruction.parameters[0],
+        T::from_address(target).ok()?,
+    ];
+
+    Some((Idiom::JumpThreading, after))
+}
+
+#[cfg(test)]
+mod optimization {
+    use super::*;
+
+    fn optimize_str(code: &str) -> Optimization<i32> {
+        optimize(&code.parse::<Program>().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn constant_folding() {
+        let optimized = optimize_str("1102,6,7,7,4,7,99,0");
+
+        assert_eq!(
+            optimized.rewrites,
+            vec![Rewrite {
+                address: 0,
+                idiom: Idiom::ConstantFold,
+                before: vec![1102, 6, 7, 7],
+                after: vec![1101, 42, 0, 7],
+            }]
+        );
+        assert_eq!(
+            optimized.rewrites[0].to_string(),
+            "0: 1102,6,7,7 -> 1101,42,0,7 (constant fold)"
+        );
+    }
+
+    #[test]
+    fn multiplying_by_zero_keeps_failing_reads() {
+        let optimized = optimize_str("1002,7,0,7,4,7,99,5");
+        assert_eq!(optimized.rewrites[0].after, vec![1101, 0, 0, 7]);
+
+        // Reading cell 100 fails, so the instruction must be kept.
+        let code = "102,0,100,7,4,7,99,5";
+        let program = code.parse::<Program>().unwrap();
+        let optimized = optimize(&program).unwrap();
+        assert!(optimized.rewrites.is_empty());
+        assert_eq!(verify(&program, &optimized.program, &[vec![]]), Ok(()));
+    }
+
+    #[test]
+    fn comparisons_fold() {
+        let optimized = optimize_str("1107,1,2,9,1108,1,2,10,99,0,0");
+
+        assert_eq!(optimized.rewrites[0].after, vec![1101, 1, 0, 9]);
+        assert_eq!(optimized.rewrites[1].after, vec![1101, 0, 0, 10]);
+    }
+
+    #[test]
+    fn copies() {
+        let optimized = optimize_str("3,13,101,0,13,14,1001,14,0,15,4,15,99,0,0,0");
+
+        assert_eq!(optimized.rewrites.len(), 1);
+        assert_eq!(optimized.rewrites[0].idiom, Idiom::Copy);
+        assert_eq!(optimized.rewrites[0].after, vec![1001, 13, 0, 14]);
+
+        // Multiplying by one costs the same as the copy, so is left alone.
+        let optimized = optimize_str("3,9,102,1,9,10,4,10,99,0,0");
+        assert!(optimized.rewrites.is_empty());
+    }
+
+    #[test]
+    fn jump_threading() {
+        // 0 jumps to 3, which always jumps to 9, past a jump that is never taken.
+        let optimized = optimize_str("1105,1,3,1105,1,6,1106,1,0,104,1,99");
+
+        assert_eq!(
+            optimized
+                .rewrites
+                .iter()
+                .map(|rewrite| (rewrite.address, rewrite.after.clone()))
+                .collect::<Vec<(usize, Vec<i32>)>>(),
+            vec![(0, vec![1105, 1, 9]), (3, vec![1105, 1, 9])]
+        );
+    }
+
+    #[test]
+    fn jump_cycles_terminate() {
+        let optimized = optimize_str("1105,1,3,1105,1,3");
+
+        assert_eq!(optimized.rewrites.len(), 0);
+    }
+
+    #[test]
+    fn cells_read_as_data_are_kept() {
+        // The output reads the folded instruction's first cell.
+        let optimized = optimize_str("1102,6,7,8,4,0,99,0,0");
+
+        assert!(optimized.rewrites.is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_code() {
+        let program = "3,6,105,1,6,99,0".parse::<Program>().unwrap();
+        assert!(optimize(&program).is_err());
+
+        let program = "1,9,10,3,2,3,11,0,99,30,40,50".parse::<Program>().unwrap();
+        assert!(optimize(&program).is_err());
+    }
+
+    #[test]
+    fn verifies_io() {
+        let code = "3,30,1102,2,3,31,1,30,31,32,1005,32,14,99,4,32,1105,1,19,1105,1,13,\
+                    0,0,0,0,0,0,0,0,0,0,0";
+        let program = code.parse::<Program>().unwrap();
+        let optimized = optimize(&program).unwrap();
+
+        assert!(!optimized.rewrites.is_empty());
+
+        let inputs = vec![vec![0], vec![-6], vec![7]];
+        assert_eq!(verify(&program, &optimized.program, &inputs), Ok(()));
+
+        let broken = "3,5,104,7,99,0".parse::<Program>().unwrap();
+        assert!(verify(&program, &broken, &inputs).is_err());
+    }
+}
@@ -0,0 +1,230 @@
+use super::scheduler::{Scheduler, SchedulerError};
+use super::{Program, ProgramState, Word};
+
+/// A network of programs whose outputs feed into each other's inputs.
+///
+/// Every value a stage outputs is copied to the input of each stage it is
+/// connected to, so stages can be chained, fanned out, merged or wired into
+/// feedback loops. Stages are run by a `Scheduler` until all of them have
+/// completed.
+#[derive(Debug)]
+pub struct Pipeline<T = i32> {
+    stages: Scheduler<T>,
+    output: Option<usize>,
+}
+
+/// The values produced by running a `Pipeline`.
+#[derive(Debug, PartialEq)]
+pub struct PipelineOutput<T> {
+    /// The last value output by the pipeline's output stage.
+    pub signal: Option<T>,
+    /// Every value output by each stage, in stage order.
+    pub history: Vec<Vec<T>>,
+}
+
+impl<T: Word> Pipeline<T> {
+    pub fn new() -> Pipeline<T> {
+        Pipeline {
+            stages: Scheduler::new(),
+            output: None,
+        }
+    }
+
+    /// Creates one copy of `program` per phase, each feeding into the next.
+    pub fn chain(program: &Program<T>, phases: &[T]) -> Pipeline<T> {
+        let mut pipeline = Pipeline::new();
+
+        for (stage, &phase) in phases.iter().enumerate() {
+            pipeline.add_stage(program.clone(), &[phase]);
+            if stage > 0 {
+                pipeline.connect(stage - 1, stage).unwrap();
+            }
+        }
+
+        pipeline
+    }
+
+    /// Like `chain`, but with the last stage feeding back into the first.
+    pub fn ring(program: &Program<T>, phases: &[T]) -> Pipeline<T> {
+        let mut pipeline = Pipeline::chain(program, phases);
+
+        if !phases.is_empty() {
+            pipeline.connect(phases.len() - 1, 0).unwrap();
+        }
+
+        pipeline
+    }
+
+    /// Adds a stage with `inputs` already queued, returning its index.
+    pub fn add_stage(&mut self, mut program: Program<T>, inputs: &[T]) -> usize {
+        inputs
+            .iter()
+            .for_each(|&value| program.push_back_input(value));
+
+        self.stages.spawn(program)
+    }
+
+    /// Feeds every output of stage `from` into the input of stage `to`.
+    pub fn connect(&mut self, from: usize, to: usize) -> Result<(), String> {
+        self.stages.connect(from, to)
+    }
+
+    /// Queues an additional input for `stage`, e.g. the initial signal.
+    pub fn push_input(&mut self, stage: usize, value: T) -> Result<(), String> {
+        self.stages.push_input(stage, value)
+    }
+
+    /// Selects the stage whose last output is the pipeline's signal. Defaults
+    /// to the most recently added stage.
+    pub fn set_output(&mut self, stage: usize) -> Result<(), String> {
+        if stage >= self.stages.len() {
+            return Err(format!(
+                "Stage {} does not exist, the pipeline has {} stages",
+                stage,
+                self.stages.len()
+            ));
+        }
+
+        self.output = Some(stage);
+        Ok(())
+    }
+
+    /// Runs every stage to completion.
+    pub fn run(self) -> Result<PipelineOutput<T>, SchedulerError> {
+        self.run_observed(|_, _| {})
+    }
+
+    /// Like `run`, but calls `observe` with the state each stage pauses in
+    /// every time it is run.
+    pub fn run_observed<F>(mut self, observe: F) -> Result<PipelineOutput<T>, SchedulerError>
+    where
+        F: FnMut(usize, &ProgramState<T>),
+    {
+        let history = self.stages.run_observed(observe)?;
+
+        let signal = match self.output.or_else(|| history.len().checked_sub(1)) {
+            Some(stage) => history[stage].last().copied(),
+            None => None,
+        };
+
+        Ok(PipelineOutput { signal, history })
+    }
+}
+
+impl<T: Word> Default for Pipeline<T> {
+    fn default() -> Pipeline<T> {
+        Pipeline::new()
+    }
+}
+
+#[cfg(test)]
+mod pipelines {
+    use super::*;
+
+    const IDENTITY: &str = "3,5,4,5,99,0";
+    const DOUBLE: &str = "3,9,102,2,9,9,4,9,99,0";
+    const SUM: &str = "3,11,3,12,1,11,12,13,4,13,99,0,0,0";
+
+    fn program(code: &str) -> Program {
+        code.parse().unwrap()
+    }
+
+    #[test]
+    fn chain() {
+        let code = program("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0");
+        let mut pipeline = Pipeline::chain(&code, &[4, 3, 2, 1, 0]);
+        pipeline.push_input(0, 0).unwrap();
+
+        let output = pipeline.run().unwrap();
+        assert_eq!(output.signal, Some(43210));
+        assert_eq!(
+            output.history,
+            vec![vec![4], vec![43], vec![432], vec![4321], vec![43210]]
+        );
+    }
+
+    #[test]
+    fn ring() {
+        let code = program(
+            "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,\
+             27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+        );
+        let mut pipeline = Pipeline::ring(&code, &[9, 8, 7, 6, 5]);
+        pipeline.push_input(0, 0).unwrap();
+
+        let output = pipeline.run().unwrap();
+        assert_eq!(output.signal, Some(139_629_729));
+        assert!(output.history.iter().all(|outputs| outputs.len() == 5));
+    }
+
+    #[test]
+    fn fan_out_and_in() {
+        let mut pipeline = Pipeline::new();
+        let source = pipeline.add_stage(program(IDENTITY), &[5]);
+        let left = pipeline.add_stage(program(DOUBLE), &[]);
+        let right = pipeline.add_stage(program(SUM), &[100]);
+        let sink = pipeline.add_stage(program(SUM), &[]);
+
+        pipeline.connect(source, left).unwrap();
+        pipeline.connect(source, right).unwrap();
+        pipeline.connect(left, sink).unwrap();
+        pipeline.connect(right, sink).unwrap();
+
+        let output = pipeline.run().unwrap();
+        assert_eq!(output.signal, Some(115));
+        assert_eq!(
+            output.history,
+            vec![vec![5], vec![10], vec![105], vec![115]]
+        );
+    }
+
+    #[test]
+    fn output_stage() {
+        let mut pipeline = Pipeline::new();
+        let first = pipeline.add_stage(program(DOUBLE), &[4]);
+        pipeline.add_stage(program(IDENTITY), &[7]);
+        pipeline.set_output(first).unwrap();
+
+        assert_eq!(pipeline.run().unwrap().signal, Some(8));
+    }
+
+    #[test]
+    fn deadlock() {
+        let pipeline = Pipeline::ring(&program(SUM), &[1, 2]);
+
+        assert_eq!(
+            pipeline.run(),
+            Err(SchedulerError::Deadlock { cycle: vec![0, 1] })
+        );
+    }
+
+    #[test]
+    fn stage_errors() {
+        let mut pipeline = Pipeline::new();
+        pipeline.add_stage(program("3,2,0"), &[]);
+
+        assert!(pipeline.connect(0, 1).is_err());
+        assert!(pipeline.push_input(2, 0).is_err());
+        assert!(pipeline.set_output(1).is_err());
+
+        pipeline.push_input(0, 42).unwrap();
+        assert_eq!(
+            pipeline.run(),
+            Err(SchedulerError::Failed {
+                program: 0,
+                error: "Unknown opcode 42 received".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            Pipeline::<i64>::new().run(),
+            Ok(PipelineOutput {
+                signal: None,
+                history: Vec::new()
+            })
+        );
+    }
+}
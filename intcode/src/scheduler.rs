@@ -0,0 +1,353 @@
+use super::{Program, ProgramState, Word};
+
+/// Runs interconnected programs in turn, routing every output to the inputs
+/// of the programs it is connected to.
+#[derive(Debug)]
+pub struct Scheduler<T = i32> {
+    programs: Vec<Program<T>>,
+    targets: Vec<Vec<usize>>,
+}
+
+/// Why a `Scheduler` could not run all of its programs to completion.
+#[derive(Debug, PartialEq)]
+pub enum SchedulerError {
+    /// The programs are each waiting for input from the next one, and the
+    /// last is waiting for the first.
+    Deadlock { cycle: Vec<usize> },
+    /// The program is waiting for input, but every program that could
+    /// provide it has completed.
+    Starved { program: usize },
+    /// The program stopped with an error.
+    Failed { program: usize, error: String },
+}
+
+impl std::fmt::Display for SchedulerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchedulerError::Deadlock { cycle } => {
+                let cycle = cycle
+                    .iter()
+                    .chain(cycle.first())
+                    .map(|program| program.to_string())
+                    .collect::<Vec<String>>();
+                write!(
+                    f,
+                    "Programs {} are waiting on each other for input",
+                    cycle.join(" -> ")
+                )
+            }
+            SchedulerError::Starved { program } => write!(
+                f,
+                "Program {} is waiting for input that no program can provide",
+                program
+            ),
+            SchedulerError::Failed { program, error } => {
+                write!(f, "Program {} failed: {}", program, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SchedulerError {}
+
+impl<T: Word> Scheduler<T> {
+    pub fn new() -> Scheduler<T> {
+        Scheduler {
+            programs: Vec::new(),
+            targets: Vec::new(),
+        }
+    }
+
+    /// Adds a program, returning its index.
+    pub fn spawn(&mut self, program: Program<T>) -> usize {
+        self.programs.push(program);
+        self.targets.push(Vec::new());
+
+        self.programs.len() - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.programs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.programs.is_empty()
+    }
+
+    /// Feeds every output of program `from` into the input of program `to`.
+    pub fn connect(&mut self, from: usize, to: usize) -> Result<(), String> {
+        self.check(to)?;
+        self.check(from)?;
+
+        self.targets[from].push(to);
+        Ok(())
+    }
+
+    pub fn push_input(&mut self, program: usize, value: T) -> Result<(), String> {
+        self.check(program)?;
+
+        self.programs[program].push_back_input(value);
+        Ok(())
+    }
+
+    /// Runs every program to completion, returning the values each output.
+    ///
+    /// Fails as soon as a program errors, or once every remaining program is
+    /// waiting for input that will never arrive.
+    pub fn run(&mut self) -> Result<Vec<Vec<T>>, SchedulerError> {
+        self.run_observed(|_, _| {})
+    }
+
+    /// Like `run`, but calls `observe` with the state each program pauses in
+    /// every time it is run, including the error that stops the scheduler.
+    pub fn run_observed<F>(&mut self, mut observe: F) -> Result<Vec<Vec<T>>, SchedulerError>
+    where
+        F: FnMut(usize, &ProgramState<T>),
+    {
+        let count = self.programs.len();
+        let mut history = vec![Vec::new(); count];
+        let mut complete = vec![false; count];
+        let mut waiting = vec![false; count];
+
+        while complete.iter().any(|&complete| !complete) {
+            let runnable = (0..count)
+                .filter(|&program| !complete[program] && !waiting[program])
+                .collect::<Vec<usize>>();
+
+            if runnable.is_empty() {
+                return Err(self.diagnose(&waiting));
+            }
+
+            for program in runnable {
+                let state = self.programs[program].execute_until_output();
+                observe(program, &state);
+
+                match state {
+                    ProgramState::Output(value) => {
+                        history[program].push(value);
+                        for &target in self.targets[program].iter() {
+                            self.programs[target].push_back_input(value);
+                            waiting[target] = false;
+                        }
+                    }
+                    ProgramState::RequiresInput => waiting[program] = true,
+                    ProgramState::Complete => complete[program] = true,
+                    ProgramState::Err(error) => {
+                        return Err(SchedulerError::Failed { program, error })
+                    }
+                }
+            }
+        }
+
+        Ok(history)
+    }
+
+    /// Looks for a cycle among the waiting programs, each waiting on a
+    /// waiting program upstream of it, which is reported wherever it is. With
+    /// no cycle, some waiting program has no waiting program upstream, so all
+    /// of its sources have completed.
+    fn diagnose(&self, waiting: &[bool]) -> SchedulerError {
+        let count = self.programs.len();
+        let mut visited = vec![false; count];
+
+        for program in (0..count).filter(|&program| waiting[program]) {
+            if let Some(cycle) = self.find_cycle(program, waiting, &mut visited, &mut Vec::new()) {
+                return SchedulerError::Deadlock { cycle };
+            }
+        }
+
+        let program = (0..count)
+            .find(|&program| {
+                waiting[program] && self.waiting_upstream(program, waiting).next().is_none()
+            })
+            .expect("waiting programs without a cycle have a first one");
+        SchedulerError::Starved { program }
+    }
+
+    /// Searches depth first upstream from `program` through waiting programs
+    /// for a cycle, skipping programs `visited` by earlier searches, which
+    /// lead to none.
+    fn find_cycle(
+        &self,
+        program: usize,
+        waiting: &[bool],
+        visited: &mut [bool],
+        path: &mut Vec<usize>,
+    ) -> Option<Vec<usize>> {
+        if let Some(start) = path.iter().position(|&visited| visited == program) {
+            return Some(path[start..].to_vec());
+        }
+        if visited[program] {
+            return None;
+        }
+        visited[program] = true;
+
+        path.push(program);
+        for source in self.waiting_upstream(program, waiting) {
+            if let Some(cycle) = self.find_cycle(source, waiting, visited, path) {
+                return Some(cycle);
+            }
+        }
+        path.pop();
+
+        None
+    }
+
+    /// The waiting programs whose outputs are sent to `program`.
+    fn waiting_upstream<'a>(
+        &'a self,
+        program: usize,
+        waiting: &'a [bool],
+    ) -> impl Iterator<Item = usize> + 'a {
+        (0..self.programs.len())
+            .filter(move |&source| waiting[source] && self.targets[source].contains(&program))
+    }
+
+    fn check(&self, program: usize) -> Result<(), String> {
+        if program < self.programs.len() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Program {} does not exist, the scheduler has {} programs",
+                program,
+                self.programs.len()
+            ))
+        }
+    }
+}
+
+impl<T: Word> Default for Scheduler<T> {
+    fn default() -> Scheduler<T> {
+        Scheduler::new()
+    }
+}
+
+#[cfg(test)]
+mod scheduling {
+    use super::*;
+
+    const IDENTITY: &str = "3,5,4,5,99,0";
+    const SUM: &str = "3,11,3,12,1,11,12,13,4,13,99,0,0,0";
+
+    fn scheduler(programs: &[&str], links: &[(usize, usize)]) -> Scheduler {
+        let mut scheduler = Scheduler::new();
+        for program in programs {
+            scheduler.spawn(program.parse().unwrap());
+        }
+        for &(from, to) in links {
+            scheduler.connect(from, to).unwrap();
+        }
+        scheduler
+    }
+
+    #[test]
+    fn routes_outputs() {
+        let mut scheduler = scheduler(&[IDENTITY, SUM, SUM], &[(0, 1), (0, 2), (1, 2)]);
+        scheduler.push_input(0, 3).unwrap();
+        scheduler.push_input(1, 4).unwrap();
+
+        assert_eq!(scheduler.run(), Ok(vec![vec![3], vec![7], vec![10]]));
+    }
+
+    #[test]
+    fn deadlock() {
+        let mut scheduler = scheduler(&[SUM, SUM], &[(0, 1), (1, 0)]);
+        scheduler.push_input(0, 1).unwrap();
+        scheduler.push_input(1, 2).unwrap();
+
+        let error = scheduler.run().unwrap_err();
+        assert_eq!(error, SchedulerError::Deadlock { cycle: vec![0, 1] });
+        assert_eq!(
+            error.to_string(),
+            "Programs 0 -> 1 -> 0 are waiting on each other for input"
+        );
+    }
+
+    #[test]
+    fn reports_only_the_cycle() {
+        // Program 0 waits on the ring formed by programs 1 and 2.
+        let mut scheduler = scheduler(&[IDENTITY, IDENTITY, SUM], &[(1, 0), (1, 2), (2, 1)]);
+
+        assert_eq!(
+            scheduler.run(),
+            Err(SchedulerError::Deadlock { cycle: vec![1, 2] })
+        );
+    }
+
+    #[test]
+    fn cycle_behind_a_starved_program() {
+        // Programs 0 and 1 are starved, with nothing upstream of 1, while 2
+        // and 3 wait on each other. The cycle is reported whichever order the
+        // programs were spawned in.
+        let programs = [IDENTITY, IDENTITY, SUM, SUM];
+        let mut starved_first = scheduler(&programs, &[(1, 0), (2, 3), (3, 2)]);
+        assert_eq!(
+            starved_first.run(),
+            Err(SchedulerError::Deadlock { cycle: vec![2, 3] })
+        );
+
+        let programs = [SUM, SUM, IDENTITY, IDENTITY];
+        let mut cycle_first = scheduler(&programs, &[(0, 1), (1, 0), (3, 2)]);
+        assert_eq!(
+            cycle_first.run(),
+            Err(SchedulerError::Deadlock { cycle: vec![0, 1] })
+        );
+    }
+
+    #[test]
+    fn starved() {
+        let mut scheduler = scheduler(&[IDENTITY, SUM], &[(0, 1)]);
+        scheduler.push_input(0, 1).unwrap();
+
+        let error = scheduler.run().unwrap_err();
+        assert_eq!(error, SchedulerError::Starved { program: 1 });
+        assert_eq!(
+            error.to_string(),
+            "Program 1 is waiting for input that no program can provide"
+        );
+    }
+
+    #[test]
+    fn failed() {
+        let mut scheduler = scheduler(&[IDENTITY, "3,2,0"], &[(0, 1)]);
+        scheduler.push_input(0, 42).unwrap();
+
+        assert_eq!(
+            scheduler.run(),
+            Err(SchedulerError::Failed {
+                program: 1,
+                error: "Unknown opcode 42 received".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn observes_states() {
+        let mut scheduler = scheduler(&[IDENTITY, SUM], &[(0, 1)]);
+        scheduler.push_input(0, 3).unwrap();
+        scheduler.push_input(1, 4).unwrap();
+
+        let mut states = Vec::new();
+        scheduler
+            .run_observed(|program, state| states.push((program, state.clone())))
+            .unwrap();
+
+        assert_eq!(
+            states,
+            vec![
+                (0, ProgramState::Output(3)),
+                (1, ProgramState::Output(7)),
+                (0, ProgramState::Complete),
+                (1, ProgramState::Complete),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_programs() {
+        let mut scheduler = scheduler(&[IDENTITY], &[]);
+
+        assert!(scheduler.connect(0, 1).is_err());
+        assert!(scheduler.push_input(1, 0).is_err());
+    }
+}
@@ -0,0 +1,243 @@
+/// A phase setting and the signal it produced.
+#[derive(Debug, PartialEq)]
+pub struct Candidate<P, S> {
+    pub phases: Vec<P>,
+    pub signal: S,
+}
+
+/// A phase setting that could not be evaluated.
+#[derive(Debug, PartialEq)]
+pub struct Failure<P, E> {
+    pub phases: Vec<P>,
+    pub error: E,
+}
+
+/// Outcome of a phase search.
+#[derive(Debug, PartialEq)]
+pub struct Search<P, S, E> {
+    /// The best candidates, strongest signal first. Ties keep the order in
+    /// which the permutations were generated.
+    pub top: Vec<Candidate<P, S>>,
+    /// Every permutation that failed, in the order they were generated.
+    pub failures: Vec<Failure<P, E>>,
+}
+
+impl<P, S, E> Search<P, S, E> {
+    pub fn best(&self) -> Option<&Candidate<P, S>> {
+        self.top.first()
+    }
+}
+
+/// Every ordered selection of `length` elements of `alphabet`, in
+/// lexicographic order of their positions.
+pub fn permutations<P: Copy>(alphabet: &[P], length: usize) -> Vec<Vec<P>> {
+    if length == 0 {
+        return vec![Vec::new()];
+    }
+
+    (0..alphabet.len())
+        .flat_map(|position| {
+            let mut rest = alphabet.to_vec();
+            let first = rest.remove(position);
+
+            permutations(&rest, length - 1)
+                .into_iter()
+                .map(move |mut tail| {
+                    tail.insert(0, first);
+                    tail
+                })
+        })
+        .collect()
+}
+
+/// Evaluates every assignment of distinct phases from `alphabet` to
+/// `amplifiers` amplifiers on all available cores, keeping the `top` best.
+pub fn search<P, S, E, F>(
+    alphabet: &[P],
+    amplifiers: usize,
+    top: usize,
+    evaluate: F,
+) -> Search<P, S, E>
+where
+    P: Copy + Send + Sync,
+    S: Ord + Send,
+    E: Send,
+    F: Fn(&[P]) -> Result<S, E> + Sync,
+{
+    let threads = std::thread::available_parallelism().map_or(1, |threads| threads.get());
+
+    search_with_threads(alphabet, amplifiers, top, threads, evaluate)
+}
+
+/// Like `search`, but spread over exactly `threads` threads.
+pub fn search_with_threads<P, S, E, F>(
+    alphabet: &[P],
+    amplifiers: usize,
+    top: usize,
+    threads: usize,
+    evaluate: F,
+) -> Search<P, S, E>
+where
+    P: Copy + Send + Sync,
+    S: Ord + Send,
+    E: Send,
+    F: Fn(&[P]) -> Result<S, E> + Sync,
+{
+    let candidates = permutations(alphabet, amplifiers);
+    let threads = threads.clamp(1, candidates.len().max(1));
+
+    let mut results = std::thread::scope(|scope| {
+        let handles = (0..threads)
+            .map(|thread| {
+                let (candidates, evaluate) = (&candidates, &evaluate);
+
+                scope.spawn(move || {
+                    candidates
+                        .iter()
+                        .enumerate()
+                        .skip(thread)
+                        .step_by(threads)
+                        .map(|(index, phases)| (index, evaluate(phases)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect::<Vec<_>>()
+    });
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut ranked = Vec::new();
+    let mut failures = Vec::new();
+    for ((_, result), phases) in results.into_iter().zip(candidates) {
+        match result {
+            Ok(signal) => ranked.push(Candidate { phases, signal }),
+            Err(error) => failures.push(Failure { phases, error }),
+        }
+    }
+
+    ranked.sort_by(|a, b| b.signal.cmp(&a.signal));
+    ranked.truncate(top);
+
+    Search {
+        top: ranked,
+        failures,
+    }
+}
+
+#[cfg(test)]
+mod phase_search {
+    use super::*;
+    use crate::pipeline::Pipeline;
+    use crate::Program;
+
+    fn chain(code: &Program, phases: &[i32]) -> Result<i32, String> {
+        let mut pipeline = Pipeline::chain(code, phases);
+        pipeline.push_input(0, 0)?;
+
+        pipeline
+            .run()
+            .map_err(|err| err.to_string())?
+            .signal
+            .ok_or_else(|| "No signal".to_string())
+    }
+
+    #[test]
+    fn permutation_counts() {
+        assert_eq!(permutations(&[0, 1, 2, 3, 4], 5).len(), 120);
+        assert_eq!(permutations(&[0, 1, 2, 3, 4], 3).len(), 60);
+        assert_eq!(permutations(&[1, 2], 3).len(), 0);
+        assert_eq!(
+            permutations(&['a', 'b', 'c'], 2),
+            vec![
+                vec!['a', 'b'],
+                vec!['a', 'c'],
+                vec!['b', 'a'],
+                vec!['b', 'c'],
+                vec!['c', 'a'],
+                vec!['c', 'b'],
+            ]
+        );
+    }
+
+    #[test]
+    fn best_sequence() {
+        let code = "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0"
+            .parse::<Program>()
+            .unwrap();
+        let search = search(&[0, 1, 2, 3, 4], 5, 3, |phases| chain(&code, phases));
+
+        assert_eq!(
+            search.best(),
+            Some(&Candidate {
+                phases: vec![4, 3, 2, 1, 0],
+                signal: 43210
+            })
+        );
+        assert_eq!(
+            search
+                .top
+                .iter()
+                .map(|candidate| candidate.signal)
+                .collect::<Vec<i32>>(),
+            vec![43210, 43201, 43120]
+        );
+        assert!(search.failures.is_empty());
+    }
+
+    #[test]
+    fn failures() {
+        let search = search(&[1, 2, 3], 2, 10, |phases| match phases {
+            [first, second] if first > second => Err(format!("{} > {}", first, second)),
+            _ => Ok(phases[0] * 10 + phases[1]),
+        });
+
+        assert_eq!(
+            search
+                .top
+                .iter()
+                .map(|candidate| candidate.signal)
+                .collect::<Vec<i32>>(),
+            vec![23, 13, 12]
+        );
+        assert_eq!(
+            search.failures,
+            vec![
+                Failure {
+                    phases: vec![2, 1],
+                    error: "2 > 1".to_string()
+                },
+                Failure {
+                    phases: vec![3, 1],
+                    error: "3 > 1".to_string()
+                },
+                Failure {
+                    phases: vec![3, 2],
+                    error: "3 > 2".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn thread_count_does_not_matter() {
+        let evaluate = |phases: &[u8]| -> Result<u32, ()> {
+            Ok(phases.iter().fold(0, |acc, &phase| acc * 7 + phase as u32) % 13)
+        };
+
+        let single = search_with_threads(&[0, 1, 2, 3, 4, 5], 4, 20, 1, evaluate);
+        for threads in [2, 3, 8, 1000].iter() {
+            assert_eq!(
+                search_with_threads(&[0, 1, 2, 3, 4, 5], 4, 20, *threads, evaluate),
+                single
+            );
+        }
+    }
+}
@@ -0,0 +1,423 @@
+//! Ahead-of-time translation of intcode programs into Rust source.
+//!
+//! Every instruction that can be decoded from the program's memory is turned
+//! into native code, dispatched on the instruction pointer. Anything else is
+//! left to the interpreter through `interpret`: instructions that were never
+//! decoded, jumps into the middle of an instruction, and instructions whose
+//! cells have been overwritten at runtime.
+
+use super::analysis::{reachable, Instruction, Target};
+use super::extension::Extensions;
+use super::{OpCode, ParamterMode, Program, ProgramState, Word};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write;
+
+/// Result of translating a `Program`.
+pub struct Translation {
+    /// Source of a Rust module exposing the program's initial `MEMORY` and a
+    /// `run` function executing it.
+    pub source: String,
+    /// Addresses of the instructions compiled to native code.
+    pub compiled: Vec<usize>,
+}
+
+/// Translates `program` into a Rust module called `name`.
+///
+/// The generated code depends on the `intcode` crate for its fallback
+/// interpreter. Programs with extension opcodes cannot be translated.
+pub fn translate<T: Word>(program: &Program<T>, name: &str) -> Result<Translation, String> {
+    if !program.extensions.is_empty() {
+        return Err("Programs with extension opcodes cannot be translated".to_string());
+    }
+
+    let memory = &program.int_codes;
+    let word = std::any::type_name::<T>();
+
+    let (mut instructions, computed) = reachable(memory, vec![0, program.i_ptr]);
+    if computed {
+        // Any value in memory could be the target of a computed jump.
+        let seeds = memory
+            .iter()
+            .filter_map(|value| value.to_address().ok())
+            .filter(|address| *address < memory.len())
+            .filter(|address| !instructions.contains_key(address))
+            .collect::<Vec<usize>>();
+        instructions.append(&mut reachable(memory, seeds).0);
+    }
+
+    instructions.retain(|_, instruction| compilable(instruction, memory.len()));
+
+    let mut covering = BTreeMap::<usize, Vec<usize>>::new();
+    for instruction in instructions.values() {
+        for cell in instruction.cells() {
+            covering.entry(cell).or_default().push(instruction.address);
+        }
+    }
+
+    let mut arms = String::new();
+    for instruction in instructions.values() {
+        write_arm(&mut arms, instruction, word, &covering).unwrap();
+    }
+
+    let source = render(name, word, memory, &arms, &covering);
+
+    Ok(Translation {
+        source,
+        compiled: instructions.keys().copied().collect(),
+    })
+}
+
+/// Executes the single instruction at `*ip` on behalf of translated code.
+///
+/// Returns the address the instruction wrote to, if any, or the state the
+/// program halted in. `*ip` is left unchanged if the program requires input.
+pub fn interpret<T: Word>(
+    memory: &mut Vec<T>,
+    ip: &mut usize,
+    input: &mut dyn FnMut() -> Option<T>,
+    output: &mut dyn FnMut(T),
+) -> Result<Option<usize>, ProgramState<T>> {
+    let instruction = Instruction::decode(memory, *ip);
+    let target = instruction.as_ref().and_then(Instruction::write_target);
+
+    let mut program = Program {
+        int_codes: std::mem::take(memory),
+        input: VecDeque::new(),
+        output: VecDeque::new(),
+        i_ptr: *ip,
+        extensions: Extensions::new(),
+    };
+
+    if let Some(OpCode::INPUT) = instruction.map(|instruction| instruction.opcode) {
+        match input() {
+            Some(value) => program.push_back_input(value),
+            None => {
+                *memory = program.int_codes;
+                return Err(ProgramState::RequiresInput);
+            }
+        }
+    }
+
+    let result = program.step(false);
+
+    *memory = program.int_codes;
+    *ip = program.i_ptr;
+    program.output.into_iter().for_each(output);
+
+    match result {
+        Ok(None) => Ok(target),
+        Ok(Some(state)) => Err(state),
+        Err(err) => Err(ProgramState::Err(err)),
+    }
+}
+
+/// Whether the instruction can be compiled: computed jumps are left to the
+/// interpreter, and every cell accessed through a constant address must be in
+/// bounds so the native code can index memory without checks.
+fn compilable<T: Word>(instruction: &Instruction<T>, len: usize) -> bool {
+    let addresses_valid = instruction
+        .reads()
+        .into_iter()
+        .chain(instruction.write_target())
+        .all(|address| address < len);
+    let target_valid = !matches!(
+        instruction.flow().1,
+        Some(Target::Computed(_)) | Some(Target::Invalid)
+    );
+    let write_valid = match instruction.opcode {
+        OpCode::INPUT
+        | OpCode::ADD(..)
+        | OpCode::MULTIPLY(..)
+        | OpCode::LESSTHAN(..)
+        | OpCode::EQUALS(..) => instruction.write_target().is_some(),
+        _ => true,
+    };
+
+    addresses_valid && target_valid && write_valid
+}
+
+fn operand<T: Word>(mode: &ParamterMode, parameter: T, word: &str) -> String {
+    match mode {
+        ParamterMode::POSITION => format!("memory[{}]", parameter),
+        ParamterMode::IMMEDIATE => format!("{}{}", parameter, word),
+    }
+}
+
+fn write_arm<T: Word>(
+    out: &mut String,
+    instruction: &Instruction<T>,
+    word: &str,
+    covering: &BTreeMap<usize, Vec<usize>>,
+) -> std::fmt::Result {
+    let p = &instruction.parameters;
+    let next = instruction.next();
+
+    writeln!(out, "                    {} => {{", instruction.address)?;
+
+    match &instruction.opcode {
+        OpCode::ADD(mode_1, mode_2)
+        | OpCode::MULTIPLY(mode_1, mode_2)
+        | OpCode::LESSTHAN(mode_1, mode_2)
+        | OpCode::EQUALS(mode_1, mode_2) => {
+            let x = operand(mode_1, p[0], word);
+            let y = operand(mode_2, p[1], word);
+            let value = match instruction.opcode {
+                OpCode::ADD(..) => checked(out, "checked_add", "adding", &x, &y)?,
+                OpCode::MULTIPLY(..) => checked(out, "checked_mul", "multiplying", &x, &y)?,
+                OpCode::LESSTHAN(..) => format!("({} < {}) as {}", x, y, word),
+                _ => format!("({} == {}) as {}", x, y, word),
+            };
+
+            writeln!(out, "                        memory[{}] = {};", p[2], value)?;
+            write_invalidate(out, instruction, covering)?;
+            writeln!(out, "                        *ip = {};", next)?;
+        }
+        OpCode::INPUT => {
+            writeln!(out, "                        match input() {{")?;
+            writeln!(
+                out,
+                "                            Some(value) => memory[{}] = value,",
+                p[0]
+            )?;
+            writeln!(
+                out,
+                "                            None => return ProgramState::RequiresInput,"
+            )?;
+            writeln!(out, "                        }}")?;
+            write_invalidate(out, instruction, covering)?;
+            writeln!(out, "                        *ip = {};", next)?;
+        }
+        OpCode::OUTPUT(mode_1) => {
+            writeln!(
+                out,
+                "                        output({});",
+                operand(mode_1, p[0], word)
+            )?;
+            writeln!(out, "                        *ip = {};", next)?;
+        }
+        OpCode::TJUMP(mode_1, _) | OpCode::FJUMP(mode_1, _) => match instruction.flow() {
+            (false, Some(Target::Static(target))) => {
+                writeln!(out, "                        *ip = {};", target)?;
+            }
+            (true, Some(Target::Static(target))) => {
+                let comparison = match instruction.opcode {
+                    OpCode::TJUMP(..) => "!=",
+                    _ => "==",
+                };
+                let condition = operand(mode_1, p[0], word);
+
+                writeln!(
+                    out,
+                    "                        if {} {} 0{} {{",
+                    condition, comparison, word
+                )?;
+                writeln!(out, "                            *ip = {};", target)?;
+                writeln!(out, "                        }} else {{")?;
+                writeln!(out, "                            *ip = {};", next)?;
+                writeln!(out, "                        }}")?;
+            }
+            _ => writeln!(out, "                        *ip = {};", next)?,
+        },
+        OpCode::EXIT => writeln!(
+            out,
+            "                        return ProgramState::Complete;"
+        )?,
+        OpCode::EXTENSION(..) => unreachable!("extension opcodes are never decoded"),
+    }
+
+    if !matches!(instruction.opcode, OpCode::EXIT) {
+        writeln!(out, "                        continue;")?;
+    }
+    writeln!(out, "                    }}")
+}
+
+/// Writes the checked arithmetic `method` on `x` and `y` into a `value`,
+/// halting with the interpreter's error if it overflows, and returns the
+/// expression to store.
+fn checked(
+    out: &mut String,
+    method: &str,
+    verb: &str,
+    x: &str,
+    y: &str,
+) -> Result<String, std::fmt::Error> {
+    writeln!(
+        out,
+        "                        let value = match {}.{}({}) {{",
+        x, method, y
+    )?;
+    writeln!(out, "                            Some(value) => value,")?;
+    writeln!(
+        out,
+        "                            None => return ProgramState::Err(format!(\"Overflow {} {{}} and {{}}\", {}, {})),",
+        verb, x, y
+    )?;
+    writeln!(out, "                        }};")?;
+    Ok("value".to_string())
+}
+
+fn write_invalidate<T: Word>(
+    out: &mut String,
+    instruction: &Instruction<T>,
+    covering: &BTreeMap<usize, Vec<usize>>,
+) -> std::fmt::Result {
+    match instruction.write_target() {
+        Some(target) if covering.contains_key(&target) => writeln!(
+            out,
+            "                        invalidate(&mut stale, {});",
+            target
+        ),
+        _ => Ok(()),
+    }
+}
+
+fn render<T: Word>(
+    name: &str,
+    word: &str,
+    memory: &[T],
+    arms: &str,
+    covering: &BTreeMap<usize, Vec<usize>>,
+) -> String {
+    let cells = memory
+        .chunks(16)
+        .map(|chunk| {
+            let values = chunk.iter().map(T::to_string).collect::<Vec<String>>();
+            format!("        {},\n", values.join(", "))
+        })
+        .collect::<String>();
+
+    // Merge runs of consecutive cells covered by the same instructions.
+    let mut ranges: Vec<(usize, usize, &Vec<usize>)> = Vec::new();
+    for (&cell, starts) in covering {
+        match ranges.last_mut() {
+            Some((_, end, last)) if *end + 1 == cell && *last == starts => *end = cell,
+            _ => ranges.push((cell, cell, starts)),
+        }
+    }
+    let invalidations = ranges
+        .iter()
+        .map(|(start, end, starts)| {
+            let cells = if start == end {
+                start.to_string()
+            } else {
+                format!("{}..={}", start, end)
+            };
+            let starts = starts.iter().map(usize::to_string).collect::<Vec<String>>();
+            format!("            {} => &[{}],\n", cells, starts.join(", "))
+        })
+        .collect::<String>();
+
+    let mut out = String::new();
+    let lines = [
+        "/// Translated from an intcode program by `intcode::translate`.",
+        "#[allow(clippy::all)]",
+        &format!("pub mod {} {{", name),
+        "    use intcode::ProgramState;",
+        "",
+        &format!("    pub const MEMORY: [{}; {}] = [", word, memory.len()),
+        cells.trim_end_matches('\n'),
+        "    ];",
+        "",
+        "    /// Runs the program on `memory` starting at `*ip`, returning once it",
+        "    /// halts. Execution can be resumed after `ProgramState::RequiresInput`.",
+        "    /// Instructions whose cells differ from `MEMORY` are interpreted.",
+        "    pub fn run(",
+        &format!("        memory: &mut Vec<{}>,", word),
+        "        ip: &mut usize,",
+        &format!("        input: &mut dyn FnMut() -> Option<{}>,", word),
+        &format!("        output: &mut dyn FnMut({}),", word),
+        &format!("    ) -> ProgramState<{}> {{", word),
+        "        if memory.len() != MEMORY.len() {",
+        "            let err = format!(\"Expected {} cells of memory\", MEMORY.len());",
+        "            return ProgramState::Err(err);",
+        "        }",
+        "",
+        "        let mut stale = vec![false; MEMORY.len()];",
+        "        for (address, (current, original)) in memory.iter().zip(MEMORY.iter()).enumerate() {",
+        "            if current != original {",
+        "                invalidate(&mut stale, address);",
+        "            }",
+        "        }",
+        "",
+        "        loop {",
+        "            if *ip < stale.len() && !stale[*ip] {",
+        "                match *ip {",
+        arms.trim_end_matches('\n'),
+        "                    _ => {}",
+        "                }",
+        "            }",
+        "",
+        "            match intcode::translate::interpret(memory, ip, input, output) {",
+        "                Ok(Some(address)) => invalidate(&mut stale, address),",
+        "                Ok(None) => {}",
+        "                Err(state) => return state,",
+        "            }",
+        "        }",
+        "    }",
+        "",
+        "    /// Marks the compiled instructions covering `address` as stale.",
+        "    fn invalidate(stale: &mut [bool], address: usize) {",
+        "        let starts: &[usize] = match address {",
+        invalidations.trim_end_matches('\n'),
+        "            _ => &[],",
+        "        };",
+        "",
+        "        for &start in starts {",
+        "            stale[start] = true;",
+        "        }",
+        "    }",
+        "}",
+    ];
+
+    for line in lines.iter() {
+        out.push_str(line);
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod translation {
+    use super::*;
+
+    #[test]
+    fn rejects_extensions() {
+        let mut program = "99".parse::<Program>().unwrap();
+        program.register_extension(50, 0, |_| Ok(())).unwrap();
+
+        assert!(translate(&program, "extended").is_err());
+    }
+
+    #[test]
+    fn compiles_reachable_code() {
+        // The data cell at 3 decodes as an instruction, but is jumped over.
+        let program = "1105,1,4,2,104,7,99".parse::<Program>().unwrap();
+
+        assert_eq!(translate(&program, "skip").unwrap().compiled, vec![0, 4, 6]);
+    }
+
+    #[test]
+    fn interpret_single_steps() {
+        let mut memory = vec![3, 5, 4, 5, 99, 0];
+        let mut ip = 0;
+        let mut outputs = Vec::new();
+
+        let result = interpret(&mut memory, &mut ip, &mut || None, &mut |v| outputs.push(v));
+        assert_eq!(result, Err(ProgramState::RequiresInput));
+        assert_eq!(ip, 0);
+
+        let result = interpret(&mut memory, &mut ip, &mut || Some(8), &mut |v| {
+            outputs.push(v)
+        });
+        assert_eq!(result, Ok(Some(5)));
+        assert_eq!((ip, memory[5]), (2, 8));
+
+        let result = interpret(&mut memory, &mut ip, &mut || None, &mut |v| outputs.push(v));
+        assert_eq!(result, Ok(None));
+        assert_eq!(outputs, vec![8]);
+
+        let result = interpret(&mut memory, &mut ip, &mut || None, &mut |v| outputs.push(v));
+        assert_eq!(result, Err(ProgramState::Complete));
+    }
+}
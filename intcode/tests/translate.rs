@@ -0,0 +1,190 @@
+//! Compiles the checked in translations under `tests/translated` and checks
+//! them against both the translator and the interpreter. Run with
+//! `INTCODE_BLESS=1` to regenerate the translations.
+
+use intcode::{Program, ProgramState};
+use std::collections::VecDeque;
+
+mod translated {
+    include!("translated/programs.rs");
+}
+
+type Run = fn(
+    &mut Vec<i32>,
+    &mut usize,
+    &mut dyn FnMut() -> Option<i32>,
+    &mut dyn FnMut(i32),
+) -> ProgramState<i32>;
+
+const COMPARE: &str = "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,\
+                       1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,\
+                       999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99";
+
+const SELF_MODIFYING: &str = "1,9,10,3,2,3,11,0,99,30,40,50";
+
+const PATCHED_OUTPUT: &str = "1101,42,0,5,104,0,99";
+
+const FEEDBACK: &str = "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,\
+                        27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5";
+
+const OVERFLOW: &str = "3,9,1002,9,65536,10,4,10,99,0,0";
+
+fn translations() -> String {
+    [
+        ("compare", COMPARE),
+        ("self_modifying", SELF_MODIFYING),
+        ("patched_output", PATCHED_OUTPUT),
+        ("feedback", FEEDBACK),
+        ("overflow", OVERFLOW),
+    ]
+    .iter()
+    .map(|(name, code)| {
+        let program = code.parse::<Program>().unwrap();
+        intcode::translate::translate(&program, name)
+            .unwrap()
+            .source
+    })
+    .collect::<Vec<String>>()
+    .join("\n")
+}
+
+fn run_translated(run: Run, memory: &mut Vec<i32>, input: &[i32]) -> (ProgramState, Vec<i32>) {
+    let mut input = input.iter().copied();
+    let mut output = Vec::new();
+
+    let state = run(memory, &mut 0, &mut || input.next(), &mut |v| {
+        output.push(v)
+    });
+    (state, output)
+}
+
+fn run_interpreted(code: &str, input: &[i32]) -> (ProgramState, Vec<i32>) {
+    let mut program = code.parse::<Program>().unwrap();
+    input.iter().for_each(|&v| program.push_back_input(v));
+
+    let mut outputs = program.outputs();
+    let output = outputs.by_ref().collect::<Vec<i32>>();
+    let state = match outputs.state() {
+        Some(ProgramState::Complete) => ProgramState::Complete,
+        Some(ProgramState::RequiresInput) => ProgramState::RequiresInput,
+        Some(ProgramState::Err(err)) => ProgramState::Err(err.clone()),
+        state => panic!("Unexpected state {:?}", state),
+    };
+    (state, output)
+}
+
+#[test]
+fn translations_are_current() {
+    let source = translations();
+
+    if std::env::var_os("INTCODE_BLESS").is_some() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/translated/programs.rs");
+        std::fs::write(path, &source).unwrap();
+    } else {
+        assert!(
+            source == include_str!("translated/programs.rs"),
+            "Translations are out of date, rerun with INTCODE_BLESS=1"
+        );
+    }
+}
+
+#[test]
+fn compare() {
+    for input in 0..=16 {
+        let mut memory = translated::compare::MEMORY.to_vec();
+
+        assert_eq!(
+            run_translated(translated::compare::run, &mut memory, &[input]),
+            run_interpreted(COMPARE, &[input])
+        );
+    }
+
+    let mut memory = translated::compare::MEMORY.to_vec();
+    assert_eq!(
+        run_translated(translated::compare::run, &mut memory, &[]),
+        (ProgramState::RequiresInput, vec![])
+    );
+}
+
+#[test]
+fn self_modifying() {
+    // Both instructions write into the first one's cells.
+    let mut memory = translated::self_modifying::MEMORY.to_vec();
+    run_translated(translated::self_modifying::run, &mut memory, &[]);
+
+    assert_eq!(memory, vec![3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50]);
+
+    // Modified memory before the run, as with day 2's noun and verb.
+    let mut memory = translated::self_modifying::MEMORY.to_vec();
+    memory[1] = 10;
+    memory[2] = 11;
+    run_translated(translated::self_modifying::run, &mut memory, &[]);
+
+    let mut program = "1,10,11,3,2,3,11,0,99,30,40,50".parse::<Program>().unwrap();
+    program.execute();
+    assert_eq!(memory[0], program.get_zero());
+
+    // The output instruction's parameter is patched before it runs.
+    let mut memory = translated::patched_output::MEMORY.to_vec();
+    assert_eq!(
+        run_translated(translated::patched_output::run, &mut memory, &[]),
+        (ProgramState::Complete, vec![42])
+    );
+}
+
+#[test]
+fn overflow() {
+    for input in [1, 32767, 32768, -32769, i32::MAX] {
+        let mut memory = translated::overflow::MEMORY.to_vec();
+
+        assert_eq!(
+            run_translated(translated::overflow::run, &mut memory, &[input]),
+            run_interpreted(OVERFLOW, &[input])
+        );
+    }
+
+    let mut memory = translated::overflow::MEMORY.to_vec();
+    assert_eq!(
+        run_translated(translated::overflow::run, &mut memory, &[32768]),
+        (
+            ProgramState::Err("Overflow multiplying 32768 and 65536".to_string()),
+            vec![]
+        )
+    );
+}
+
+#[test]
+fn feedback_resumes() {
+    let phases = [9, 8, 7, 6, 5];
+    let mut amplifiers = phases
+        .iter()
+        .map(|&phase| {
+            let memory = translated::feedback::MEMORY.to_vec();
+            (memory, 0, VecDeque::from(vec![phase]))
+        })
+        .collect::<Vec<(Vec<i32>, usize, VecDeque<i32>)>>();
+
+    let mut signal = 0;
+    'feedback: loop {
+        for (memory, ip, input) in amplifiers.iter_mut() {
+            input.push_back(signal);
+
+            let mut output = Vec::new();
+            let state =
+                translated::feedback::run(memory, ip, &mut || input.pop_front(), &mut |v| {
+                    output.push(v)
+                });
+            if let Some(&last) = output.last() {
+                signal = last;
+            }
+
+            assert!(state == ProgramState::Complete || state == ProgramState::RequiresInput);
+        }
+
+        if amplifiers.iter().all(|(memory, ip, _)| memory[*ip] == 99) {
+            break 'feedback;
+        }
+    }
+
+    assert_eq!(signal, 139_629_729);
+}
@@ -0,0 +1,541 @@
+/// Translated from an intcode program by `intcode::translate`.
+#[allow(clippy::all)]
+pub mod compare {
+    use intcode::ProgramState;
+
+    pub const MEMORY: [i32; 47] = [
+        3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31,
+        1106, 0, 36, 98, 0, 0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104,
+        999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20, 1105, 1, 46, 98, 99,
+    ];
+
+    /// Runs the program on `memory` starting at `*ip`, returning once it
+    /// halts. Execution can be resumed after `ProgramState::RequiresInput`.
+    /// Instructions whose cells differ from `MEMORY` are interpreted.
+    pub fn run(
+        memory: &mut Vec<i32>,
+        ip: &mut usize,
+        input: &mut dyn FnMut() -> Option<i32>,
+        output: &mut dyn FnMut(i32),
+    ) -> ProgramState<i32> {
+        if memory.len() != MEMORY.len() {
+            let err = format!("Expected {} cells of memory", MEMORY.len());
+            return ProgramState::Err(err);
+        }
+
+        let mut stale = vec![false; MEMORY.len()];
+        for (address, (current, original)) in memory.iter().zip(MEMORY.iter()).enumerate() {
+            if current != original {
+                invalidate(&mut stale, address);
+            }
+        }
+
+        loop {
+            if *ip < stale.len() && !stale[*ip] {
+                match *ip {
+                    0 => {
+                        match input() {
+                            Some(value) => memory[21] = value,
+                            None => return ProgramState::RequiresInput,
+                        }
+                        *ip = 2;
+                        continue;
+                    }
+                    2 => {
+                        memory[20] = (memory[21] == 8i32) as i32;
+                        *ip = 6;
+                        continue;
+                    }
+                    6 => {
+                        if memory[20] != 0i32 {
+                            *ip = 22;
+                        } else {
+                            *ip = 9;
+                        }
+                        continue;
+                    }
+                    9 => {
+                        memory[20] = (8i32 < memory[21]) as i32;
+                        *ip = 13;
+                        continue;
+                    }
+                    13 => {
+                        if memory[20] == 0i32 {
+                            *ip = 31;
+                        } else {
+                            *ip = 16;
+                        }
+                        continue;
+                    }
+                    16 => {
+                        *ip = 36;
+                        continue;
+                    }
+                    22 => {
+                        let value = match memory[21].checked_mul(125i32) {
+                            Some(value) => value,
+                            None => return ProgramState::Err(format!("Overflow multiplying {} and {}", memory[21], 125i32)),
+                        };
+                        memory[20] = value;
+                        *ip = 26;
+                        continue;
+                    }
+                    26 => {
+                        output(memory[20]);
+                        *ip = 28;
+                        continue;
+                    }
+                    28 => {
+                        *ip = 46;
+                        continue;
+                    }
+                    31 => {
+                        output(999i32);
+                        *ip = 33;
+                        continue;
+                    }
+                    33 => {
+                        *ip = 46;
+                        continue;
+                    }
+                    36 => {
+                        let value = match 1000i32.checked_add(1i32) {
+                            Some(value) => value,
+                            None => return ProgramState::Err(format!("Overflow adding {} and {}", 1000i32, 1i32)),
+                        };
+                        memory[20] = value;
+                        *ip = 40;
+                        continue;
+                    }
+                    40 => {
+                        output(memory[20]);
+                        *ip = 42;
+                        continue;
+                    }
+                    42 => {
+                        *ip = 46;
+                        continue;
+                    }
+                    46 => {
+                        return ProgramState::Complete;
+                    }
+                    _ => {}
+                }
+            }
+
+            match intcode::translate::interpret(memory, ip, input, output) {
+                Ok(Some(address)) => invalidate(&mut stale, address),
+                Ok(None) => {}
+                Err(state) => return state,
+            }
+        }
+    }
+
+    /// Marks the compiled instructions covering `address` as stale.
+    fn invalidate(stale: &mut [bool], address: usize) {
+        let starts: &[usize] = match address {
+            0..=1 => &[0],
+            2..=5 => &[2],
+            6..=8 => &[6],
+            9..=12 => &[9],
+            13..=15 => &[13],
+            16..=18 => &[16],
+            22..=25 => &[22],
+            26..=27 => &[26],
+            28..=30 => &[28],
+            31..=32 => &[31],
+            33..=35 => &[33],
+            36..=39 => &[36],
+            40..=41 => &[40],
+            42..=44 => &[42],
+            46 => &[46],
+            _ => &[],
+        };
+
+        for &start in starts {
+            stale[start] = true;
+        }
+    }
+}
+
+/// Translated from an intcode program by `intcode::translate`.
+#[allow(clippy::all)]
+pub mod self_modifying {
+    use intcode::ProgramState;
+
+    pub const MEMORY: [i32; 12] = [
+        1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50,
+    ];
+
+    /// Runs the program on `memory` starting at `*ip`, returning once it
+    /// halts. Execution can be resumed after `ProgramState::RequiresInput`.
+    /// Instructions whose cells differ from `MEMORY` are interpreted.
+    pub fn run(
+        memory: &mut Vec<i32>,
+        ip: &mut usize,
+        input: &mut dyn FnMut() -> Option<i32>,
+        output: &mut dyn FnMut(i32),
+    ) -> ProgramState<i32> {
+        if memory.len() != MEMORY.len() {
+            let err = format!("Expected {} cells of memory", MEMORY.len());
+            return ProgramState::Err(err);
+        }
+
+        let mut stale = vec![false; MEMORY.len()];
+        for (address, (current, original)) in memory.iter().zip(MEMORY.iter()).enumerate() {
+            if current != original {
+                invalidate(&mut stale, address);
+            }
+        }
+
+        loop {
+            if *ip < stale.len() && !stale[*ip] {
+                match *ip {
+                    0 => {
+                        let value = match memory[9].checked_add(memory[10]) {
+                            Some(value) => value,
+                            None => return ProgramState::Err(format!("Overflow adding {} and {}", memory[9], memory[10])),
+                        };
+                        memory[3] = value;
+                        invalidate(&mut stale, 3);
+                        *ip = 4;
+                        continue;
+                    }
+                    4 => {
+                        let value = match memory[3].checked_mul(memory[11]) {
+                            Some(value) => value,
+                            None => return ProgramState::Err(format!("Overflow multiplying {} and {}", memory[3], memory[11])),
+                        };
+                        memory[0] = value;
+                        invalidate(&mut stale, 0);
+                        *ip = 8;
+                        continue;
+                    }
+                    8 => {
+                        return ProgramState::Complete;
+                    }
+                    _ => {}
+                }
+            }
+
+            match intcode::translate::interpret(memory, ip, input, output) {
+                Ok(Some(address)) => invalidate(&mut stale, address),
+                Ok(None) => {}
+                Err(state) => return state,
+            }
+        }
+    }
+
+    /// Marks the compiled instructions covering `address` as stale.
+    fn invalidate(stale: &mut [bool], address: usize) {
+        let starts: &[usize] = match address {
+            0..=3 => &[0],
+            4..=7 => &[4],
+            8 => &[8],
+            _ => &[],
+        };
+
+        for &start in starts {
+            stale[start] = true;
+        }
+    }
+}
+
+/// Translated from an intcode program by `intcode::translate`.
+#[allow(clippy::all)]
+pub mod patched_output {
+    use intcode::ProgramState;
+
+    pub const MEMORY: [i32; 7] = [
+        1101, 42, 0, 5, 104, 0, 99,
+    ];
+
+    /// Runs the program on `memory` starting at `*ip`, returning once it
+    /// halts. Execution can be resumed after `ProgramState::RequiresInput`.
+    /// Instructions whose cells differ from `MEMORY` are interpreted.
+    pub fn run(
+        memory: &mut Vec<i32>,
+        ip: &mut usize,
+        input: &mut dyn FnMut() -> Option<i32>,
+        output: &mut dyn FnMut(i32),
+    ) -> ProgramState<i32> {
+        if memory.len() != MEMORY.len() {
+            let err = format!("Expected {} cells of memory", MEMORY.len());
+            return ProgramState::Err(err);
+        }
+
+        let mut stale = vec![false; MEMORY.len()];
+        for (address, (current, original)) in memory.iter().zip(MEMORY.iter()).enumerate() {
+            if current != original {
+                invalidate(&mut stale, address);
+            }
+        }
+
+        loop {
+            if *ip < stale.len() && !stale[*ip] {
+                match *ip {
+                    0 => {
+                        let value = match 42i32.checked_add(0i32) {
+                            Some(value) => value,
+                            None => return ProgramState::Err(format!("Overflow adding {} and {}", 42i32, 0i32)),
+                        };
+                        memory[5] = value;
+                        invalidate(&mut stale, 5);
+                        *ip = 4;
+                        continue;
+                    }
+                    4 => {
+                        output(0i32);
+                        *ip = 6;
+                        continue;
+                    }
+                    6 => {
+                        return ProgramState::Complete;
+                    }
+                    _ => {}
+                }
+            }
+
+            match intcode::translate::interpret(memory, ip, input, output) {
+                Ok(Some(address)) => invalidate(&mut stale, address),
+                Ok(None) => {}
+                Err(state) => return state,
+            }
+        }
+    }
+
+    /// Marks the compiled instructions covering `address` as stale.
+    fn invalidate(stale: &mut [bool], address: usize) {
+        let starts: &[usize] = match address {
+            0..=3 => &[0],
+            4..=5 => &[4],
+            6 => &[6],
+            _ => &[],
+        };
+
+        for &start in starts {
+            stale[start] = true;
+        }
+    }
+}
+
+/// Translated from an intcode program by `intcode::translate`.
+#[allow(clippy::all)]
+pub mod feedback {
+    use intcode::ProgramState;
+
+    pub const MEMORY: [i32; 29] = [
+        3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27,
+        4, 27, 1001, 28, -1, 28, 1005, 28, 6, 99, 0, 0, 5,
+    ];
+
+    /// Runs the program on `memory` starting at `*ip`, returning once it
+    /// halts. Execution can be resumed after `ProgramState::RequiresInput`.
+    /// Instructions whose cells differ from `MEMORY` are interpreted.
+    pub fn run(
+        memory: &mut Vec<i32>,
+        ip: &mut usize,
+        input: &mut dyn FnMut() -> Option<i32>,
+        output: &mut dyn FnMut(i32),
+    ) -> ProgramState<i32> {
+        if memory.len() != MEMORY.len() {
+            let err = format!("Expected {} cells of memory", MEMORY.len());
+            return ProgramState::Err(err);
+        }
+
+        let mut stale = vec![false; MEMORY.len()];
+        for (address, (current, original)) in memory.iter().zip(MEMORY.iter()).enumerate() {
+            if current != original {
+                invalidate(&mut stale, address);
+            }
+        }
+
+        loop {
+            if *ip < stale.len() && !stale[*ip] {
+                match *ip {
+                    0 => {
+                        match input() {
+                            Some(value) => memory[26] = value,
+                            None => return ProgramState::RequiresInput,
+                        }
+                        *ip = 2;
+                        continue;
+                    }
+                    2 => {
+                        let value = match memory[26].checked_add(-4i32) {
+                            Some(value) => value,
+                            None => return ProgramState::Err(format!("Overflow adding {} and {}", memory[26], -4i32)),
+                        };
+                        memory[26] = value;
+                        *ip = 6;
+                        continue;
+                    }
+                    6 => {
+                        match input() {
+                            Some(value) => memory[27] = value,
+                            None => return ProgramState::RequiresInput,
+                        }
+                        *ip = 8;
+                        continue;
+                    }
+                    8 => {
+                        let value = match memory[27].checked_mul(2i32) {
+                            Some(value) => value,
+                            None => return ProgramState::Err(format!("Overflow multiplying {} and {}", memory[27], 2i32)),
+                        };
+                        memory[27] = value;
+                        *ip = 12;
+                        continue;
+                    }
+                    12 => {
+                        let value = match memory[27].checked_add(memory[26]) {
+                            Some(value) => value,
+                            None => return ProgramState::Err(format!("Overflow adding {} and {}", memory[27], memory[26])),
+                        };
+                        memory[27] = value;
+                        *ip = 16;
+                        continue;
+                    }
+                    16 => {
+                        output(memory[27]);
+                        *ip = 18;
+                        continue;
+                    }
+                    18 => {
+                        let value = match memory[28].checked_add(-1i32) {
+                            Some(value) => value,
+                            None => return ProgramState::Err(format!("Overflow adding {} and {}", memory[28], -1i32)),
+                        };
+                        memory[28] = value;
+                        *ip = 22;
+                        continue;
+                    }
+                    22 => {
+                        if memory[28] != 0i32 {
+                            *ip = 6;
+                        } else {
+                            *ip = 25;
+                        }
+                        continue;
+                    }
+                    25 => {
+                        return ProgramState::Complete;
+                    }
+                    _ => {}
+                }
+            }
+
+            match intcode::translate::interpret(memory, ip, input, output) {
+                Ok(Some(address)) => invalidate(&mut stale, address),
+                Ok(None) => {}
+                Err(state) => return state,
+            }
+        }
+    }
+
+    /// Marks the compiled instructions covering `address` as stale.
+    fn invalidate(stale: &mut [bool], address: usize) {
+        let starts: &[usize] = match address {
+            0..=1 => &[0],
+            2..=5 => &[2],
+            6..=7 => &[6],
+            8..=11 => &[8],
+            12..=15 => &[12],
+            16..=17 => &[16],
+            18..=21 => &[18],
+            22..=24 => &[22],
+            25 => &[25],
+            _ => &[],
+        };
+
+        for &start in starts {
+            stale[start] = true;
+        }
+    }
+}
+
+/// Translated from an intcode program by `intcode::translate`.
+#[allow(clippy::all)]
+pub mod overflow {
+    use intcode::ProgramState;
+
+    pub const MEMORY: [i32; 11] = [
+        3, 9, 1002, 9, 65536, 10, 4, 10, 99, 0, 0,
+    ];
+
+    /// Runs the program on `memory` starting at `*ip`, returning once it
+    /// halts. Execution can be resumed after `ProgramState::RequiresInput`.
+    /// Instructions whose cells differ from `MEMORY` are interpreted.
+    pub fn run(
+        memory: &mut Vec<i32>,
+        ip: &mut usize,
+        input: &mut dyn FnMut() -> Option<i32>,
+        output: &mut dyn FnMut(i32),
+    ) -> ProgramState<i32> {
+        if memory.len() != MEMORY.len() {
+            let err = format!("Expected {} cells of memory", MEMORY.len());
+            return ProgramState::Err(err);
+        }
+
+        let mut stale = vec![false; MEMORY.len()];
+        for (address, (current, original)) in memory.iter().zip(MEMORY.iter()).enumerate() {
+            if current != original {
+                invalidate(&mut stale, address);
+            }
+        }
+
+        loop {
+            if *ip < stale.len() && !stale[*ip] {
+                match *ip {
+                    0 => {
+                        match input() {
+                            Some(value) => memory[9] = value,
+                            None => return ProgramState::RequiresInput,
+                        }
+                        *ip = 2;
+                        continue;
+                    }
+                    2 => {
+                        let value = match memory[9].checked_mul(65536i32) {
+                            Some(value) => value,
+                            None => return ProgramState::Err(format!("Overflow multiplying {} and {}", memory[9], 65536i32)),
+                        };
+                        memory[10] = value;
+                        *ip = 6;
+                        continue;
+                    }
+                    6 => {
+                        output(memory[10]);
+                        *ip = 8;
+                        continue;
+                    }
+                    8 => {
+                        return ProgramState::Complete;
+                    }
+                    _ => {}
+                }
+            }
+
+            match intcode::translate::interpret(memory, ip, input, output) {
+                Ok(Some(address)) => invalidate(&mut stale, address),
+                Ok(None) => {}
+                Err(state) => return state,
+            }
+        }
+    }
+
+    /// Marks the compiled instructions covering `address` as stale.
+    fn invalidate(stale: &mut [bool], address: usize) {
+        let starts: &[usize] = match address {
+            0..=1 => &[0],
+            2..=5 => &[2],
+            6..=7 => &[6],
+            8 => &[8],
+            _ => &[],
+        };
+
+        for &start in starts {
+            stale[start] = true;
+        }
+    }
+}
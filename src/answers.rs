@@ -0,0 +1,224 @@
+use crate::parse::ParseError;
+use std::collections::BTreeMap;
+
+/// The known-good answers shipped with the repository.
+pub const ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/inputs/answers");
+
+/// Expected answers by day, part and input name.
+///
+/// Each non-empty line that is not a `#` comment holds one answer as
+/// `DAY PART INPUT "ANSWER"`, where the answer is quoted and escapes `\n`,
+/// `\"` and `\\` so that multi-line answers fit on a line. Input names are
+/// quoted the same way when they hold whitespace.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    expected: BTreeMap<(u8, u8, String), String>,
+}
+
+impl Answers {
+    pub fn load(path: &str) -> Result<Answers, String> {
+        std::fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read answers file {}: {}", path, err))?
+            .parse()
+            .map_err(|err| format!("Invalid answers file {}: {}", path, err))
+    }
+
+    pub fn get(&self, day: u8, part: u8, input: &str) -> Option<&str> {
+        self.expected
+            .get(&(day, part, input.to_string()))
+            .map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.expected.is_empty()
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, input: &str, answer: String) {
+        self.expected.insert((day, part, input.to_string()), answer);
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+
+        for line in s.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fail = |text: &str, reason: &str| ParseError::at(s, text, reason);
+            let mut fields = line.splitn(3, ' ');
+            let mut field =
+                |name: &str| fields.next().ok_or_else(|| fail(&line[line.len()..], name));
+
+            let day = field("missing day")?;
+            let part = field("missing part")?;
+            let rest = field("missing input")?;
+
+            let (input, answer) = if rest.starts_with('"') {
+                let end = closing_quote(rest)
+                    .ok_or_else(|| fail(rest, "input name is missing its closing quote"))?;
+                rest.split_at(end + 1)
+            } else {
+                rest.split_at(rest.find(' ').unwrap_or(rest.len()))
+            };
+            let answer = answer
+                .strip_prefix(' ')
+                .ok_or_else(|| fail(answer, "missing answer"))?;
+            let input = if input.starts_with('"') {
+                unquote(input).ok_or_else(|| fail(input, "invalid quoted input name"))?
+            } else {
+                input.to_string()
+            };
+
+            let day_number = day.parse().map_err(|_| fail(day, "invalid day"))?;
+            let part = match part {
+                "1" => 1,
+                "2" => 2,
+                _ => return Err(fail(part, "part must be 1 or 2")),
+            };
+            let expected =
+                unquote(answer).ok_or_else(|| fail(answer, "answer must be a quoted string"))?;
+
+            if answers.get(day_number, part, &input).is_some() {
+                return Err(fail(line, "duplicate answer"));
+            }
+            answers.insert(day_number, part, &input, expected);
+        }
+
+        Ok(answers)
+    }
+}
+
+impl std::fmt::Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((day, part, input), answer) in self.expected.iter() {
+            let input = if input.is_empty()
+                || input.starts_with('"')
+                || input.contains(char::is_whitespace)
+            {
+                quote(input)
+            } else {
+                input.clone()
+            };
+            writeln!(f, "{} {} {} {}", day, part, input, quote(answer))?;
+        }
+        Ok(())
+    }
+}
+
+fn quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+
+    format!("\"{}\"", escaped)
+}
+
+fn unquote(quoted: &str) -> Option<String> {
+    if quoted.len() < 2 || !quoted.starts_with('"') || !quoted.ends_with('"') {
+        return None;
+    }
+
+    let mut answer = String::new();
+    let mut chars = quoted[1..quoted.len() - 1].chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                'n' => answer.push('\n'),
+                escaped @ '"' | escaped @ '\\' => answer.push(escaped),
+                _ => return None,
+            },
+            '"' => return None,
+            c => answer.push(c),
+        }
+    }
+
+    Some(answer)
+}
+
+/// The index of the quote closing the quoted string that `quoted` starts
+/// with, skipping escaped quotes.
+fn closing_quote(quoted: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in quoted.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod expected {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let answers = "# day part input answer\n\
+                       7 1 day_07 \"255840\"\n\
+                       \n\
+                       8 2 day_08 \" X\\n\\\"\\\\\"\n\
+                       8 1 \"my input\" \"1\"\n"
+            .parse::<Answers>()
+            .unwrap();
+
+        assert_eq!(answers.get(7, 1, "day_07"), Some("255840"));
+        assert_eq!(answers.get(8, 2, "day_08"), Some(" X\n\"\\"));
+        assert_eq!(answers.get(7, 2, "day_07"), None);
+        assert_eq!(answers.get(7, 1, "example"), None);
+        assert_eq!(answers.get(8, 1, "my input"), Some("1"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut answers = Answers::default();
+        answers.insert(1, 1, "day_01", "3188480".to_string());
+        answers.insert(8, 2, "day_08", " XX \nX  \"\\".to_string());
+        answers.insert(7, 1, "inputs/my input", "2".to_string());
+        answers.insert(7, 2, "\"quoted\" \\ name", "3".to_string());
+        answers.insert(7, 2, "", "4".to_string());
+
+        assert!(answers
+            .to_string()
+            .contains("7 1 \"inputs/my input\" \"2\"\n"));
+
+        assert_eq!(answers.to_string().parse(), Ok(answers));
+    }
+
+    #[test]
+    fn invalid() {
+        let error = |s: &str| s.parse::<Answers>().unwrap_err().to_string();
+
+        assert_eq!(error("\n7 1 day_07"), "2:11: missing answer at \"\"");
+        assert_eq!(
+            error("7 1 \"my input \"1\""),
+            "1:16: missing answer at \"1\\\"\""
+        );
+        assert_eq!(
+            error("7 1 \"my input"),
+            "1:5: input name is missing its closing quote at \"\\\"my input\""
+        );
+        assert_eq!(
+            error("7 3 day_07 \"1\""),
+            "1:3: part must be 1 or 2 at \"3\""
+        );
+        assert_eq!(error("x 1 day_07 \"1\""), "1:1: invalid day at \"x\"");
+        assert_eq!(
+            error("7 1 day_07 255840"),
+            "1:12: answer must be a quoted string at \"255840\""
+        );
+        assert_eq!(
+            error("7 1 day_07 \"1\"\n7 1 day_07 \"2\""),
+            "2:1: duplicate answer at \"7 1 day_07 \\\"2\\\"\""
+        );
+    }
+}
@@ -0,0 +1,320 @@
+use crate::parse::ParseError;
+use crate::Solution;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Where `aoc bench` keeps the results of its latest run.
+pub const LATEST_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/target/bench/latest");
+
+/// How long to spend timing each benchmark.
+#[derive(Debug, Clone, Copy)]
+pub struct Budget {
+    pub time: Duration,
+    pub min_samples: usize,
+}
+
+impl Default for Budget {
+    fn default() -> Budget {
+        Budget {
+            time: Duration::from_millis(500),
+            min_samples: 10,
+        }
+    }
+}
+
+/// Summary of the durations of a benchmark's runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn new(mut samples: Vec<Duration>) -> Option<Stats> {
+        samples.sort();
+
+        let total = samples.iter().sum::<Duration>();
+        let mean = total.checked_div(samples.len() as u32)?;
+
+        Some(Stats {
+            samples: samples.len(),
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            mean,
+            max: samples[samples.len() - 1],
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub name: String,
+    pub stats: Stats,
+}
+
+impl Measurement {
+    pub fn new(name: &str, stats: Stats) -> Measurement {
+        Measurement {
+            name: name.to_string(),
+            stats,
+        }
+    }
+}
+
+/// Times `f` repeatedly, after one warm-up run, until `budget` is spent.
+pub fn time<R, F: FnMut() -> R>(budget: &Budget, mut f: F) -> Stats {
+    black_box(f());
+
+    let start = Instant::now();
+    let mut samples = Vec::new();
+    while samples.len() < budget.min_samples.max(1) || start.elapsed() < budget.time {
+        let run = Instant::now();
+        black_box(f());
+        samples.push(run.elapsed());
+    }
+
+    Stats::new(samples).expect("A budget always takes at least one sample")
+}
+
+/// Times parsing `input` and solving both parts, as `parse`, `part_1` and
+/// `part_2`. Fails without timing anything if either part fails.
+pub fn solution<S: Solution>(input: &str, budget: &Budget) -> Result<Vec<Measurement>, String> {
+    let solution = S::parse(input).map_err(|err| err.to_string())?;
+    solution.part_1().map_err(|err| err.to_string())?;
+    solution.part_2().map_err(|err| err.to_string())?;
+
+    Ok(vec![
+        Measurement::new("parse", time(budget, || S::parse(black_box(input)))),
+        Measurement::new("part_1", time(budget, || solution.part_1())),
+        Measurement::new("part_2", time(budget, || solution.part_2())),
+    ])
+}
+
+/// Times raw intcode execution of a tight loop, independent of any day.
+pub fn intcode(budget: &Budget) -> Vec<Measurement> {
+    let program = intcode::compiler::compile::<i64>(
+        "read n;
+         let total = 0;
+         while n > 0 {
+             total = total + n * n;
+             n = n - 1;
+         }
+         print total;",
+    )
+    .expect("The benchmark program compiles");
+
+    let stats = time(budget, || {
+        let mut program = program.clone();
+        program.push_back_input(10_000);
+        program.execute()
+    });
+
+    vec![Measurement::new("sum_of_squares", stats)]
+}
+
+/// A set of measurements, saved one per line as the name followed by the
+/// sample count and the min, median, mean and max in nanoseconds.
+#[derive(Debug, Default, PartialEq)]
+pub struct Results {
+    pub measurements: Vec<Measurement>,
+}
+
+/// How a measurement's median changed relative to a baseline.
+#[derive(Debug, PartialEq)]
+pub struct Comparison {
+    pub name: String,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Comparison {
+    /// The relative change, positive when slower. Medians too short for the
+    /// clock to measure count as a nanosecond, so that a zero baseline gives
+    /// a finite change rather than an infinite or undefined one.
+    pub fn change(&self) -> f64 {
+        let resolution = Duration::from_nanos(1);
+        self.current.max(resolution).as_secs_f64() / self.baseline.max(resolution).as_secs_f64()
+            - 1.0
+    }
+
+    pub fn regressed(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+impl Results {
+    /// Compares every measurement that is also in `baseline`.
+    pub fn compare(&self, baseline: &Results) -> Vec<Comparison> {
+        self.measurements
+            .iter()
+            .filter_map(|current| {
+                let baseline = baseline
+                    .measurements
+                    .iter()
+                    .find(|baseline| baseline.name == current.name)?;
+
+                Some(Comparison {
+                    name: current.name.clone(),
+                    baseline: baseline.stats.median,
+                    current: current.stats.median,
+                })
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Results {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for Measurement { name, stats } in self.measurements.iter() {
+            writeln!(
+                f,
+                "{} {} {} {} {} {}",
+                name,
+                stats.samples,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.mean.as_nanos(),
+                stats.max.as_nanos()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Results {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let measurement = |line: &str| -> Option<Measurement> {
+            let fields = line.split_whitespace().collect::<Vec<&str>>();
+            let nanos = |field: &str| field.parse().ok().map(Duration::from_nanos);
+
+            match fields[..] {
+                [name, samples, min, median, mean, max] => Some(Measurement {
+                    name: name.to_string(),
+                    stats: Stats {
+                        samples: samples.parse().ok()?,
+                        min: nanos(min)?,
+                        median: nanos(median)?,
+                        mean: nanos(mean)?,
+                        max: nanos(max)?,
+                    },
+                }),
+                _ => None,
+            }
+        };
+
+        let measurements = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                measurement(line).ok_or_else(|| {
+                    ParseError::at(s, line, "expected NAME SAMPLES MIN MEDIAN MEAN MAX")
+                })
+            })
+            .collect::<Result<Vec<Measurement>, ParseError>>()?;
+
+        Ok(Results { measurements })
+    }
+}
+
+#[cfg(test)]
+mod benchmarks {
+    use super::*;
+
+    fn nanos(samples: &[u64]) -> Vec<Duration> {
+        samples
+            .iter()
+            .map(|&nanos| Duration::from_nanos(nanos))
+            .collect()
+    }
+
+    fn measurement(name: &str, median: u64) -> Measurement {
+        Measurement::new(name, Stats::new(nanos(&[median])).unwrap())
+    }
+
+    #[test]
+    fn stats() {
+        assert_eq!(
+            Stats::new(nanos(&[40, 10, 30, 20, 100])),
+            Some(Stats {
+                samples: 5,
+                min: Duration::from_nanos(10),
+                median: Duration::from_nanos(30),
+                mean: Duration::from_nanos(40),
+                max: Duration::from_nanos(100),
+            })
+        );
+        assert_eq!(Stats::new(Vec::new()), None);
+    }
+
+    #[test]
+    fn budget() {
+        let budget = Budget {
+            time: Duration::from_millis(0),
+            min_samples: 3,
+        };
+
+        let mut runs = 0;
+        assert_eq!(time(&budget, || runs += 1).samples, 3);
+        assert_eq!(runs, 4);
+    }
+
+    #[test]
+    fn round_trip() {
+        let results = Results {
+            measurements: vec![
+                measurement("day_01/parse", 1234),
+                measurement("intcode/loop", 5),
+            ],
+        };
+
+        assert_eq!(
+            results.to_string(),
+            "day_01/parse 1 1234 1234 1234 1234\nintcode/loop 1 5 5 5 5\n"
+        );
+        assert_eq!(results.to_string().parse(), Ok(results));
+        assert_eq!(
+            "day_01/parse 1 1 1 1 1\nday_01/part_1 1 2 3"
+                .parse::<Results>()
+                .unwrap_err()
+                .to_string(),
+            "2:1: expected NAME SAMPLES MIN MEDIAN MEAN MAX at \"day_01/part_1 1 2 3\""
+        );
+    }
+
+    #[test]
+    fn compare() {
+        let baseline = Results {
+            measurements: vec![measurement("a", 100), measurement("b", 100)],
+        };
+        let current = Results {
+            measurements: vec![measurement("b", 150), measurement("c", 10)],
+        };
+
+        let comparisons = current.compare(&baseline);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].name, "b");
+        assert!((comparisons[0].change() - 0.5).abs() < 1e-9);
+        assert!(comparisons[0].regressed(0.1));
+        assert!(!comparisons[0].regressed(0.5));
+    }
+
+    #[test]
+    fn compare_unmeasurable() {
+        let comparison = |baseline, current| Comparison {
+            name: "a".to_string(),
+            baseline: Duration::from_nanos(baseline),
+            current: Duration::from_nanos(current),
+        };
+
+        assert_eq!(comparison(0, 0).change(), 0.0);
+        assert!(!comparison(0, 0).regressed(0.1));
+        assert!((comparison(0, 3).change() - 2.0).abs() < 1e-9);
+        assert!(comparison(0, 3).regressed(0.1));
+        assert!((comparison(100, 0).change() + 0.99).abs() < 1e-9);
+    }
+}
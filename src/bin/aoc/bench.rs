@@ -0,0 +1,298 @@
+use super::Options;
+use advent_of_code_2019::bench::{self, Budget, Comparison, Measurement, Results, LATEST_PATH};
+use advent_of_code_2019::days::{self, Day};
+use advent_of_code_2019::input::Source;
+use std::time::Duration;
+
+const USAGE: &str = "\
+Usage: aoc bench [DAYS] [--input PATH] [--time MS] [--save FILE]
+                 [--baseline FILE] [--threshold PERCENT]
+
+Times parsing and both parts of the selected days, and raw intcode
+execution when all days are selected. Each benchmark runs for at least MS
+milliseconds (default 500). Results are kept in target/bench/latest for
+aoc readme, --save also writes them to FILE, and
+--baseline compares them against a saved run, failing if any median is
+more than PERCENT (default 10) slower.";
+
+/// Runs the benchmarks, returning the exit code.
+pub fn main(args: &[String]) -> i32 {
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return 0;
+    }
+
+    let options = match BenchOptions::parse(args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            return 2;
+        }
+    };
+
+    let baseline = match options.baseline.as_ref().map(|path| load(path)) {
+        Some(Ok(baseline)) => Some(baseline),
+        Some(Err(err)) => {
+            eprintln!("{}", err);
+            return 2;
+        }
+        None => None,
+    };
+
+    let mut failed = false;
+    let mut results = Results::default();
+    for day in options.days.iter() {
+        match run(day, &options) {
+            Ok(measurements) => results.measurements.extend(measurements),
+            Err(err) => {
+                eprintln!("Day {:02}: {}", day.number, err);
+                failed = true;
+            }
+        }
+    }
+    if options.intcode {
+        let measurements = bench::intcode(&options.budget);
+        results
+            .measurements
+            .extend(prefixed("intcode", measurements));
+    }
+
+    print_results(&results);
+
+    for path in options
+        .save
+        .iter()
+        .map(String::as_str)
+        .chain(Some(LATEST_PATH))
+    {
+        if let Err(err) = save(path, &results) {
+            eprintln!("Failed to save results to {}: {}", path, err);
+            failed = true;
+        }
+    }
+
+    if let Some(baseline) = baseline {
+        let comparisons = results.compare(&baseline);
+        print_comparisons(&comparisons, options.threshold);
+        failed |= comparisons
+            .iter()
+            .any(|comparison| comparison.regressed(options.threshold));
+    }
+
+    if failed {
+        1
+    } else {
+        0
+    }
+}
+
+fn run(day: &Day, options: &BenchOptions) -> Result<Vec<Measurement>, String> {
+    let input = options
+        .input
+        .read(day.number)
+        .map_err(|err| err.to_string())?;
+    let measurements = (day.bench)(&input, &options.budget)?;
+
+    Ok(prefixed(&format!("day_{:02}", day.number), measurements))
+}
+
+fn prefixed(prefix: &str, measurements: Vec<Measurement>) -> Vec<Measurement> {
+    measurements
+        .into_iter()
+        .map(|measurement| Measurement {
+            name: format!("{}/{}", prefix, measurement.name),
+            ..measurement
+        })
+        .collect()
+}
+
+fn save(path: &str, results: &Results) -> std::io::Result<()> {
+    if let Some(dir) = std::path::Path::new(path).parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, results.to_string())
+}
+
+fn load(path: &str) -> Result<Results, String> {
+    std::fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read baseline {}: {}", path, err))?
+        .parse()
+        .map_err(|err| format!("Invalid baseline {}: {}", path, err))
+}
+
+fn print_results(results: &Results) {
+    println!(
+        "{:<24} {:>10} {:>10} {:>10} {:>10} {:>8}",
+        "Benchmark", "median", "mean", "min", "max", "samples"
+    );
+    for Measurement { name, stats } in results.measurements.iter() {
+        println!(
+            "{:<24} {:>10} {:>10} {:>10} {:>10} {:>8}",
+            name,
+            duration(stats.median),
+            duration(stats.mean),
+            duration(stats.min),
+            duration(stats.max),
+            stats.samples
+        );
+    }
+}
+
+fn print_comparisons(comparisons: &[Comparison], threshold: f64) {
+    println!("\nCompared to the baseline median:");
+    for comparison in comparisons {
+        println!(
+            "{:<24} {:>10} -> {:>10} {:>+8.1}%{}",
+            comparison.name,
+            duration(comparison.baseline),
+            duration(comparison.current),
+            comparison.change() * 100.0,
+            if comparison.regressed(threshold) {
+                "  regression"
+            } else {
+                ""
+            }
+        );
+    }
+}
+
+fn duration(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
+
+struct BenchOptions {
+    days: Vec<&'static Day>,
+    intcode: bool,
+    input: Source,
+    budget: Budget,
+    save: Option<String>,
+    baseline: Option<String>,
+    threshold: f64,
+}
+
+impl BenchOptions {
+    fn parse(args: &[String]) -> Result<BenchOptions, String> {
+        let mut days = None;
+        let mut intcode = true;
+        let mut input = Source::Default;
+        let mut budget = Budget::default();
+        let mut save = None;
+        let mut baseline = None;
+        let mut threshold = 0.1;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("Missing value after {}", arg))
+            };
+
+            match arg.as_str() {
+                "--input" | "-i" => input = Source::from_arg(value()?),
+                "--time" => {
+                    let time = value()?;
+                    let millis = time
+                        .parse()
+                        .map_err(|_| format!("Invalid time '{}'", time))?;
+                    budget.time = Duration::from_millis(millis);
+                }
+                "--save" => save = Some(value()?.clone()),
+                "--baseline" => baseline = Some(value()?.clone()),
+                "--threshold" => {
+                    let percent = value()?;
+                    threshold = percent
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|percent| *percent >= 0.0)
+                        .ok_or_else(|| format!("Invalid threshold '{}'", percent))?
+                        / 100.0;
+                }
+                flag if flag.starts_with('-') => return Err(format!("Unknown option '{}'", flag)),
+                selection if days.is_none() => {
+                    intcode = selection == "all";
+                    days = Some(Options::parse_days(selection)?);
+                }
+                extra => return Err(format!("Unexpected argument '{}'", extra)),
+            }
+        }
+
+        let days = days.unwrap_or_else(|| days::DAYS.iter().collect());
+        if days.len() > 1 && input.is_single() {
+            return Err("An input file or stdin can only be used for a single day".to_string());
+        }
+
+        Ok(BenchOptions {
+            days,
+            intcode,
+            input,
+            budget,
+            save,
+            baseline,
+            threshold,
+        })
+    }
+}
+
+#[cfg(test)]
+mod benchmarks {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<BenchOptions, String> {
+        let args = args
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<String>>();
+        BenchOptions::parse(&args)
+    }
+
+    #[test]
+    fn defaults() {
+        let options = parse(&[]).unwrap();
+
+        assert_eq!(options.days.len(), days::DAYS.len());
+        assert!(options.intcode);
+        assert_eq!(options.budget.time, Duration::from_millis(500));
+        assert_eq!(options.threshold, 0.1);
+        assert_eq!(options.save, None);
+        assert_eq!(options.baseline, None);
+    }
+
+    #[test]
+    fn options() {
+        let options = parse(&[
+            "2-3",
+            "--time",
+            "50",
+            "--save",
+            "new",
+            "--baseline",
+            "old",
+            "--threshold",
+            "25",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            options
+                .days
+                .iter()
+                .map(|day| day.number)
+                .collect::<Vec<u8>>(),
+            vec![2, 3]
+        );
+        assert!(!options.intcode);
+        assert_eq!(options.budget.time, Duration::from_millis(50));
+        assert_eq!(options.save, Some("new".to_string()));
+        assert_eq!(options.baseline, Some("old".to_string()));
+        assert_eq!(options.threshold, 0.25);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse(&["--time", "fast"]).is_err());
+        assert!(parse(&["--threshold", "-5"]).is_err());
+        assert!(parse(&["--save"]).is_err());
+        assert!(parse(&["--part", "1"]).is_err());
+        assert!(parse(&["1", "2"]).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use advent_of_code_2019::days;
+use advent_of_code_2019::fetch::{Fetched, Fetcher, BASE_URL_VAR, SESSION_VAR};
+use advent_of_code_2019::input::DEFAULT_DIR;
+use std::path::PathBuf;
+
+const USAGE: &str = "\
+Usage: aoc fetch [DAYS] [--session TOKEN] [--base-url URL] [--offline]
+                 [--refresh] [--retries N]
+
+Downloads the inputs of DAYS, a day such as 9 or a range such as 1-25,
+into src/inputs, skipping those already there unless --refresh is given.
+Without DAYS the inputs of every solved day are fetched.
+
+The session cookie is taken from TOKEN or AOC_SESSION, and inputs are
+requested from URL/day/N/input, where URL defaults to AOC_BASE_URL or
+https://adventofcode.com/2019. Rate limited requests are retried N times
+(default 3) with increasing waits. --offline only reports whether the
+inputs are cached.";
+
+/// Fetches the selected inputs, returning the exit code.
+pub fn main(args: &[String]) -> i32 {
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return 0;
+    }
+
+    let (days, fetcher) = match parse(args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            return 2;
+        }
+    };
+
+    let mut failed = false;
+    for day in days {
+        match fetcher.fetch(day) {
+            Ok(Fetched::Cached(path)) => println!("Day {:02}: cached at {}", day, path.display()),
+            Ok(Fetched::Downloaded(path)) => {
+                println!("Day {:02}: downloaded to {}", day, path.display())
+            }
+            Err(err) => {
+                eprintln!("{}", err);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        1
+    } else {
+        0
+    }
+}
+
+fn parse(args: &[String]) -> Result<(Vec<u8>, Fetcher), String> {
+    let mut days = None;
+    let mut fetcher = Fetcher::new(PathBuf::from(DEFAULT_DIR));
+    fetcher.session = std::env::var(SESSION_VAR).ok();
+    if let Ok(base_url) = std::env::var(BASE_URL_VAR) {
+        fetcher.base_url = base_url;
+    }
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value after {}", arg))
+        };
+
+        match arg.as_str() {
+            "--session" => fetcher.session = Some(value()?.clone()),
+            "--base-url" => fetcher.base_url = value()?.clone(),
+            "--offline" => fetcher.offline = true,
+            "--refresh" => fetcher.refresh = true,
+            "--retries" => {
+                let retries = value()?;
+                fetcher.retries = retries
+                    .parse()
+                    .map_err(|_| format!("Invalid retries '{}'", retries))?;
+            }
+            flag if flag.starts_with('-') => return Err(format!("Unknown option '{}'", flag)),
+            selection if days.is_none() => days = Some(parse_days(selection)?),
+            extra => return Err(format!("Unexpected argument '{}'", extra)),
+        }
+    }
+
+    let days = days.unwrap_or_else(|| days::DAYS.iter().map(|day| day.number).collect());
+    Ok((days, fetcher))
+}
+
+/// Like the runner's days, but any puzzle can be fetched, solved or not.
+fn parse_days(selection: &str) -> Result<Vec<u8>, String> {
+    let number = |s: &str| {
+        s.parse::<u8>()
+            .ok()
+            .filter(|day| (1..=25).contains(day))
+            .ok_or_else(|| format!("Invalid day '{}', expected 1 to 25", s))
+    };
+
+    let days = match selection.find('-') {
+        Some(split) => number(&selection[..split])?..=number(&selection[split + 1..])?,
+        None => number(selection)?..=number(selection)?,
+    };
+
+    if days.is_empty() {
+        Err(format!("Range '{}' contains no days", selection))
+    } else {
+        Ok(days.collect())
+    }
+}
+
+#[cfg(test)]
+mod fetching {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<(Vec<u8>, Fetcher), String> {
+        super::parse(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn options() {
+        let (days, fetcher) = parse(&[
+            "9-11",
+            "--session",
+            "abc",
+            "--base-url",
+            "http://localhost:8080",
+            "--offline",
+            "--retries",
+            "5",
+        ])
+        .unwrap();
+
+        assert_eq!(days, vec![9, 10, 11]);
+        assert_eq!(fetcher.session, Some("abc".to_string()));
+        assert_eq!(fetcher.base_url, "http://localhost:8080");
+        assert!(fetcher.offline);
+        assert!(!fetcher.refresh);
+        assert_eq!(fetcher.retries, 5);
+        assert_eq!(fetcher.cache_dir, PathBuf::from(DEFAULT_DIR));
+    }
+
+    #[test]
+    fn days() {
+        assert_eq!(parse(&[]).unwrap().0.len(), days::DAYS.len());
+        assert_eq!(parse(&["25"]).unwrap().0, vec![25]);
+        assert!(parse(&["26"]).is_err());
+        assert!(parse(&["0"]).is_err());
+        assert!(parse(&["5-3"]).is_err());
+        assert!(parse(&["--retries", "many"]).is_err());
+        assert!(parse(&["--session"]).is_err());
+    }
+}
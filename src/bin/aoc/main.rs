@@ -0,0 +1,478 @@
+mod bench;
+mod fetch;
+mod new;
+mod readme;
+
+use advent_of_code_2019::answers::{Answers, ANSWERS_PATH};
+use advent_of_code_2019::days::{self, Day};
+use advent_of_code_2019::input::{self, Source};
+use advent_of_code_2019::json::Json;
+use advent_of_code_2019::Answer;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "\
+Usage: aoc [DAYS] [--part 1|2] [--trace] [--input PATH|--all-inputs]
+           [--verify] [--json]
+       aoc bench --help
+       aoc fetch --help
+       aoc new --help
+       aoc readme --help
+
+DAYS is a day such as 7, a range such as 1-5, or all (the default).
+With --verify every answer is checked against src/inputs/answers, failing
+if any differs or is missing. With --json every part is reported as a JSON
+object on its own line, holding its answer, the answer's type, the time
+taken and any error.
+
+Inputs are read from PATH, a file for a single day, a directory holding
+day_XX files, or - for stdin. Without --input they are read from the
+directory named by AOC_INPUT_DIR, then from the inputs shipped with the
+repository or, when built with the embed-inputs feature, the binary.
+
+With --all-inputs every day is also run on the named inputs in the
+subdirectories of those directories, such as src/inputs/alice/day_07,
+whose answers are recorded under the subdirectory's name. Together with
+--verify this checks every solution against everyone's inputs.";
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+
+    match args.first().map(String::as_str) {
+        Some("bench") => std::process::exit(bench::main(&args[1..])),
+        Some("fetch") => std::process::exit(fetch::main(&args[1..])),
+        Some("new") => std::process::exit(new::main(&args[1..])),
+        Some("readme") => std::process::exit(readme::main(&args[1..])),
+        _ => {}
+    }
+
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let options = match Options::parse(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let answers = if options.verify {
+        match Answers::load(ANSWERS_PATH) {
+            Ok(answers) => Some(answers),
+            Err(err) => {
+                eprintln!("{}", err);
+                std::process::exit(2);
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut failed = false;
+    let mut missing = Answers::default();
+    for day in options.days.iter() {
+        let sources = if options.all_inputs {
+            match input::all(day.number) {
+                Ok(sources) => sources,
+                Err(err) => {
+                    eprintln!("Day {:02}: {}", day.number, err);
+                    failed = true;
+                    continue;
+                }
+            }
+        } else {
+            vec![options.input.clone()]
+        };
+
+        for source in sources.iter() {
+            failed |= !run(day, source, &options, answers.as_ref(), &mut missing);
+        }
+    }
+
+    if !missing.is_empty() && !options.json {
+        eprint!(
+            "\nNo known answers for these inputs, add them to {} once checked:\n{}",
+            ANSWERS_PATH, missing
+        );
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+/// Runs the selected parts of `day` on the input from `source`, returning
+/// whether all of them succeeded and, when verifying against `answers`,
+/// matched the expected answer. Answers found when none were known are
+/// added to `missing`.
+fn run(
+    day: &Day,
+    source: &Source,
+    options: &Options,
+    answers: Option<&Answers>,
+    missing: &mut Answers,
+) -> bool {
+    let name = source.name(day.number);
+    let label = if options.all_inputs {
+        format!("Day {:02} ({})", day.number, name)
+    } else {
+        format!("Day {:02}", day.number)
+    };
+
+    let input = match source.read(day.number) {
+        Ok(input) => input,
+        Err(err) if options.json => {
+            for &part in options.parts.iter() {
+                let fields = vec![
+                    ("day", Json::Integer(day.number.into())),
+                    ("part", Json::Integer(part.into())),
+                    ("input", name.as_str().into()),
+                    ("type", Json::Null),
+                    ("answer", Json::Null),
+                    ("error", err.to_string().into()),
+                    ("time_ns", Json::Null),
+                ];
+                println!("{}", Json::object(fields));
+            }
+            return false;
+        }
+        Err(err) => {
+            eprintln!("{}: {}", label, err);
+            return false;
+        }
+    };
+
+    let mut succeeded = true;
+    for &part in options.parts.iter() {
+        let solver = if part == 1 { day.part_1 } else { day.part_2 };
+
+        let start = Instant::now();
+        let answer = solver(&input);
+        let elapsed = start.elapsed();
+
+        let outcome = Outcome {
+            day: day.number,
+            part,
+            label: format!("{}, part {}", label, part),
+            input: name.clone(),
+            answer,
+            elapsed,
+            expected: answers
+                .map(|answers| answers.get(day.number, part, &name).map(str::to_string)),
+        };
+
+        if options.json {
+            println!("{}", outcome.json());
+        } else {
+            outcome.print();
+        }
+        if let (Ok(answer), Some(None)) = (&outcome.answer, &outcome.expected) {
+            missing.insert(day.number, part, &name, answer.to_string());
+        }
+        succeeded &= outcome.passed();
+    }
+
+    if let (true, Some(trace)) = (options.trace, day.trace) {
+        let trace = trace(&input);
+        if options.json {
+            let (trace, error) = match trace {
+                Ok(trace) => (Some(trace), None),
+                Err(err) => (None, Some(err)),
+            };
+            let fields = vec![
+                ("day", Json::Integer(day.number.into())),
+                ("input", name.as_str().into()),
+                ("trace", trace.into()),
+                ("error", error.clone().into()),
+            ];
+            println!("{}", Json::object(fields));
+            succeeded &= error.is_none();
+        } else {
+            match trace {
+                Ok(trace) => println!("{}", trace),
+                Err(err) => {
+                    eprintln!("{}, trace failed: {}", label, err);
+                    succeeded = false;
+                }
+            }
+        }
+    }
+
+    succeeded
+}
+
+/// The result of solving one part.
+struct Outcome {
+    day: u8,
+    part: u8,
+    /// Names the day and part, and the input when running several.
+    label: String,
+    input: String,
+    answer: Result<Answer, String>,
+    elapsed: Duration,
+    /// The known answer when verifying, if there is one.
+    expected: Option<Option<String>>,
+}
+
+impl Outcome {
+    /// Whether the part was solved and, when verifying, its answer is known
+    /// and matches.
+    fn passed(&self) -> bool {
+        match (&self.answer, &self.expected) {
+            (Ok(answer), Some(Some(expected))) => answer.to_string() == *expected,
+            (Ok(_), Some(None)) => false,
+            (Ok(_), None) => true,
+            (Err(_), _) => false,
+        }
+    }
+
+    fn print(&self) {
+        let (label, elapsed) = (&self.label, self.elapsed);
+
+        match (&self.answer, &self.expected) {
+            (Err(err), _) => eprintln!("{} failed: {}", label, err),
+            (Ok(_), Some(Some(_))) if self.passed() => {
+                println!("{}: ok ({:.2?})", label, elapsed)
+            }
+            (Ok(answer), Some(Some(expected))) => {
+                let answer = answer.to_string();
+                if answer.contains('\n') || expected.contains('\n') {
+                    eprintln!(
+                        "{}: wrong answer, expected\n{}\nbut got\n{}",
+                        label, expected, answer
+                    );
+                } else {
+                    eprintln!(
+                        "{}: wrong answer, expected {} but got {}",
+                        label, expected, answer
+                    );
+                }
+            }
+            (Ok(_), Some(None)) => eprintln!(
+                "{}: no known answer for input {} ({:.2?})",
+                label, self.input, elapsed
+            ),
+            (Ok(image @ Answer::Image(_)), None) => {
+                println!("{} ({:.2?}):\n{}", label, elapsed, image)
+            }
+            (Ok(answer), None) => println!("{}: {} ({:.2?})", label, answer, elapsed),
+        }
+    }
+
+    /// One object holding the answer and its type or the error, the time
+    /// taken and, when verifying, the expected answer and whether it matched.
+    fn json(&self) -> Json {
+        let (kind, answer, error) = match &self.answer {
+            Ok(answer) => (answer.kind().into(), answer.into(), Json::Null),
+            Err(err) => (Json::Null, Json::Null, err.as_str().into()),
+        };
+
+        let mut fields = vec![
+            ("day", Json::Integer(self.day.into())),
+            ("part", Json::Integer(self.part.into())),
+            ("input", self.input.as_str().into()),
+            ("type", kind),
+            ("answer", answer),
+            ("error", error),
+            ("time_ns", Json::Integer(self.elapsed.as_nanos() as i128)),
+        ];
+        if let Some(expected) = &self.expected {
+            fields.push(("expected", expected.clone().into()));
+            fields.push(("passed", self.passed().into()));
+        }
+
+        Json::object(fields)
+    }
+}
+
+struct Options {
+    days: Vec<&'static Day>,
+    parts: Vec<u8>,
+    trace: bool,
+    input: Source,
+    /// Whether to run every day on the default and every named input.
+    all_inputs: bool,
+    verify: bool,
+    json: bool,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Options, String> {
+        let mut days = None;
+        let mut parts = vec![1, 2];
+        let mut trace = false;
+        let mut input = None;
+        let mut all_inputs = false;
+        let mut verify = false;
+        let mut json = false;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" | "-p" => {
+                    parts = match args.next().map(String::as_str) {
+                        Some("1") => vec![1],
+                        Some("2") => vec![2],
+                        Some(part) => return Err(format!("Unknown part '{}'", part)),
+                        None => return Err("Missing part after --part".to_string()),
+                    }
+                }
+                "--trace" => trace = true,
+                "--verify" => verify = true,
+                "--json" => json = true,
+                "--all-inputs" => all_inputs = true,
+                "--input" | "-i" => match args.next() {
+                    Some(path) => input = Some(Source::from_arg(path)),
+                    None => return Err("Missing path after --input".to_string()),
+                },
+                flag if flag.starts_with('-') => return Err(format!("Unknown option '{}'", flag)),
+                selection if days.is_none() => days = Some(Self::parse_days(selection)?),
+                extra => return Err(format!("Unexpected argument '{}'", extra)),
+            }
+        }
+
+        if all_inputs && input.is_some() {
+            return Err("--input and --all-inputs cannot be combined".to_string());
+        }
+
+        let input = input.unwrap_or(Source::Default);
+        let days = days.unwrap_or_else(|| days::DAYS.iter().collect());
+        if days.len() > 1 && input.is_single() {
+            return Err("An input file or stdin can only be used for a single day".to_string());
+        }
+
+        Ok(Options {
+            days,
+            parts,
+            trace,
+            input,
+            all_inputs,
+            verify,
+            json,
+        })
+    }
+
+    fn parse_days(selection: &str) -> Result<Vec<&'static Day>, String> {
+        if selection == "all" {
+            return Ok(days::DAYS.iter().collect());
+        }
+
+        let number = |s: &str| s.parse::<u8>().map_err(|_| format!("Invalid day '{}'", s));
+        let (first, last) = match selection.find('-') {
+            Some(split) => (
+                number(&selection[..split])?,
+                number(&selection[split + 1..])?,
+            ),
+            None => (number(selection)?, number(selection)?),
+        };
+
+        let days = (first..=last)
+            .map(|number| days::get(number).ok_or(format!("Day {} is not solved yet", number)))
+            .collect::<Result<Vec<&'static Day>, String>>()?;
+
+        if days.is_empty() {
+            Err(format!("Range '{}' contains no days", selection))
+        } else {
+            Ok(days)
+        }
+    }
+}
+
+#[cfg(test)]
+mod aoc {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<(Vec<u8>, Vec<u8>, bool), String> {
+        let args = args
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<String>>();
+        let options = Options::parse(&args)?;
+        let days = options.days.iter().map(|day| day.number).collect();
+
+        Ok((days, options.parts, options.trace))
+    }
+
+    #[test]
+    fn defaults() {
+        assert_eq!(
+            parse(&[]),
+            Ok((
+                days::DAYS.iter().map(|day| day.number).collect(),
+                vec![1, 2],
+                false
+            ))
+        );
+        assert_eq!(parse(&["all"]), parse(&[]));
+        assert!(!Options::parse(&[]).unwrap().verify);
+        assert!(Options::parse(&["--verify".to_string()]).unwrap().verify);
+        assert!(Options::parse(&["--json".to_string()]).unwrap().json);
+        assert!(!Options::parse(&[]).unwrap().all_inputs);
+        assert!(
+            Options::parse(&["--all-inputs".to_string()])
+                .unwrap()
+                .all_inputs
+        );
+    }
+
+    #[test]
+    fn selection() {
+        assert_eq!(parse(&["7"]), Ok((vec![7], vec![1, 2], false)));
+        assert_eq!(
+            parse(&["2-4", "--part", "2"]),
+            Ok((vec![2, 3, 4], vec![2], false))
+        );
+        assert_eq!(
+            parse(&["-p", "1", "7", "--trace"]),
+            Ok((vec![7], vec![1], true))
+        );
+        assert_eq!(
+            parse(&["3", "--input", "-"]),
+            Ok((vec![3], vec![1, 2], false))
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse(&["26"]).is_err());
+        assert!(parse(&["4-2"]).is_err());
+        assert!(parse(&["x"]).is_err());
+        assert!(parse(&["1", "2"]).is_err());
+        assert!(parse(&["--part", "3"]).is_err());
+        assert!(parse(&["--part"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+        assert!(parse(&["--input"]).is_err());
+        assert!(parse(&["1-2", "--input", "-"]).is_err());
+        assert_eq!(
+            parse(&["7", "--all-inputs", "--input", "day_07"]),
+            Err("--input and --all-inputs cannot be combined".to_string())
+        );
+    }
+
+    #[test]
+    fn verification() {
+        let outcome = |answer: Result<Answer, String>, expected: Option<Option<&str>>| Outcome {
+            day: 1,
+            part: 1,
+            label: "Day 01, part 1".to_string(),
+            input: "day_01".to_string(),
+            answer,
+            elapsed: Duration::from_millis(1),
+            expected: expected.map(|expected| expected.map(str::to_string)),
+        };
+
+        assert!(outcome(Ok(Answer::from(4)), None).passed());
+        assert!(outcome(Ok(Answer::from(4)), Some(Some("4"))).passed());
+        assert!(!outcome(Ok(Answer::from(4)), Some(Some("5"))).passed());
+        assert!(!outcome(Err("failed".to_string()), None).passed());
+
+        let missing = outcome(Ok(Answer::from(4)), Some(None));
+        assert!(!missing.passed());
+        assert!(missing
+            .json()
+            .to_string()
+            .ends_with(r#""expected":null,"passed":false}"#));
+    }
+}
@@ -0,0 +1,385 @@
+use super::readme;
+use advent_of_code_2019::days;
+use std::path::Path;
+
+const USAGE: &str = "\
+Usage: aoc new DAY [--name TYPE]
+
+Creates src/days/day_XX.rs with a TYPE (default Puzzle) implementing
+Solution and ignored tests to fill in with the puzzle's examples, an empty
+src/inputs/day_XX, and registers the day, with its input for the
+embed-inputs feature, with the runner and the README.";
+
+/// Scaffolds a day, returning the exit code.
+pub fn main(args: &[String]) -> i32 {
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return 0;
+    }
+
+    let (day, name) = match parse(args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            return 2;
+        }
+    };
+
+    match scaffold(Path::new(env!("CARGO_MANIFEST_DIR")), day, &name) {
+        Ok(()) => {
+            println!(
+                "Created day {}, edit src/days/day_{:02}.rs and add the input to src/inputs/day_{:02}",
+                day, day, day
+            );
+            0
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            1
+        }
+    }
+}
+
+fn parse(args: &[String]) -> Result<(u8, String), String> {
+    let mut day = None;
+    let mut name = "Puzzle".to_string();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--name" => match args.next() {
+                Some(value) if is_type_name(value) => name = value.clone(),
+                Some(value) => return Err(format!("Invalid type name '{}'", value)),
+                None => return Err("Missing type name after --name".to_string()),
+            },
+            flag if flag.starts_with('-') => return Err(format!("Unknown option '{}'", flag)),
+            number if day.is_none() => {
+                day = number
+                    .parse()
+                    .ok()
+                    .filter(|day| (1..=25).contains(day))
+                    .map(Some)
+                    .ok_or_else(|| format!("Invalid day '{}', expected 1 to 25", number))?
+            }
+            extra => return Err(format!("Unexpected argument '{}'", extra)),
+        }
+    }
+
+    Ok((day.ok_or("Missing day")?, name))
+}
+
+fn is_type_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Writes the new day's files, after checking that every edit applies.
+fn scaffold(root: &Path, day: u8, name: &str) -> Result<(), String> {
+    if days::get(day).is_some() {
+        return Err(format!("Day {} already exists", day));
+    }
+
+    let module = root.join(format!("src/days/day_{:02}.rs", day));
+    let input = root.join(format!("src/inputs/day_{:02}", day));
+    let registry = root.join("src/days/mod.rs");
+    let readme = root.join("README.md");
+
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()));
+    }
+
+    let read = |path: &Path| {
+        std::fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read {}: {}", path.display(), err))
+    };
+    let registered = register(&read(&registry)?, day, name)?;
+    let listed = list(&read(&readme)?, day)?;
+
+    let write = |path: &Path, contents: &str| {
+        std::fs::write(path, contents)
+            .map_err(|err| format!("Failed to write {}: {}", path.display(), err))
+    };
+    write(&module, &template(day, name))?;
+    if !input.exists() {
+        write(&input, "")?;
+    }
+    write(&registry, &registered)?;
+    write(&readme, &listed)
+}
+
+fn template(day: u8, name: &str) -> String {
+    TEMPLATE
+        .replace("Puzzle", name)
+        .replace("day_XX", &format!("day_{:02}", day))
+}
+
+const TEMPLATE: &str = r#"use crate::{Answer, Error, Solution};
+
+pub struct Puzzle {}
+
+impl Solution for Puzzle {
+    type Err = Error;
+
+    fn parse(_input: &str) -> Result<Self, Self::Err> {
+        Ok(Puzzle {})
+    }
+
+    fn part_1(&self) -> Result<Answer, Self::Err> {
+        Err(Error::NoAnswer("Part 1 is not solved yet".to_string()))
+    }
+
+    fn part_2(&self) -> Result<Answer, Self::Err> {
+        Err(Error::NoAnswer("Part 2 is not solved yet".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod day_XX {
+    use super::*;
+
+    // Fill in the puzzle's examples and expected answers, then remove the
+    // #[ignore] attributes.
+    const EXAMPLE_1: &str = "";
+    const EXAMPLE_2: &str = "";
+
+    #[test]
+    #[ignore]
+    fn part_1() {
+        let puzzle = Puzzle::parse(EXAMPLE_1).unwrap();
+
+        assert_eq!(puzzle.part_1(), Ok(Answer::Integer(0)));
+    }
+
+    #[test]
+    #[ignore]
+    fn part_2() {
+        let puzzle = Puzzle::parse(EXAMPLE_2).unwrap();
+
+        assert_eq!(puzzle.part_2(), Ok(Answer::Integer(0)));
+    }
+}
+"#;
+
+/// Adds the module declaration and the `DAYS` entry for `day` to the
+/// registry in `src/days/mod.rs`, keeping both ordered by day.
+fn register(registry: &str, day: u8, name: &str) -> Result<String, String> {
+    let mut lines = registry.lines().map(str::to_string).collect::<Vec<_>>();
+
+    let declared = |line: &str| {
+        line.strip_prefix("pub mod day_")?
+            .strip_suffix(';')?
+            .parse()
+            .ok()
+    };
+    let declaration =
+        insertion(&lines, declared, day)?.ok_or("No day modules are declared in the registry")?;
+    lines.insert(declaration, format!("pub mod day_{:02};", day));
+
+    let numbered = |line: &str| {
+        line.trim()
+            .strip_prefix("number: ")?
+            .strip_suffix(',')?
+            .parse()
+            .ok()
+    };
+    let entry = insertion(&lines, numbered, day)?.ok_or("No days are listed in DAYS")?;
+    let entry = if lines.get(entry).and_then(|line| numbered(line)).is_some() {
+        // Before a later day, whose entry opens on the line above its number.
+        entry - 1
+    } else {
+        // After the last day, once its entry closes.
+        let close = lines[entry..]
+            .iter()
+            .position(|line| line == "    },")
+            .ok_or("The last DAYS entry is not closed")?;
+        entry + close + 1
+    };
+
+    let module = format!("day_{:02}::{}", day, name);
+    let block = [
+        "    Day {".to_string(),
+        format!("        number: {},", day),
+        format!("        part_1: solve_part_1::<{}>,", module),
+        format!("        part_2: solve_part_2::<{}>,", module),
+        "        trace: None,".to_string(),
+        format!("        bench: bench::solution::<{}>,", module),
+        format!("        input: embedded!(\"day_{:02}\"),", day),
+        "    },".to_string(),
+    ];
+    lines.splice(entry..entry, block.iter().cloned());
+
+    let count = lines
+        .iter_mut()
+        .find(|line| line.starts_with("pub const DAYS: [Day; "))
+        .ok_or("The registry has no DAYS array")?;
+    let days = count
+        .trim_start_matches("pub const DAYS: [Day; ")
+        .trim_end_matches("] = [")
+        .parse::<usize>()
+        .map_err(|_| format!("Unexpected DAYS declaration '{}'", count))?;
+    *count = format!("pub const DAYS: [Day; {}] = [", days + 1);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the row for `day` to the README's solutions table.
+fn list(readme: &str, day: u8) -> Result<String, String> {
+    let mut lines = readme.lines().map(str::to_string).collect::<Vec<_>>();
+
+    let (start, end) = match (
+        lines.iter().position(|line| line == readme::START),
+        lines.iter().position(|line| line == readme::END),
+    ) {
+        (Some(start), Some(end)) if start < end => (start, end),
+        _ => return Err("The README has no solutions table".to_string()),
+    };
+
+    let listed = |line: &str| line.strip_prefix("| ")?.split(' ').next()?.parse().ok();
+    let entry = insertion(&lines[start..end], listed, day)?
+        .ok_or("The README's solutions table is empty")?;
+    lines.insert(
+        start + entry,
+        readme::row(day, "unsolved", "unsolved", None),
+    );
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Where a line keyed `day` belongs among the lines `key` gives a day: before
+/// the first later day, or after the last one. `None` if no line has a key.
+fn insertion<F>(lines: &[String], key: F, day: u8) -> Result<Option<usize>, String>
+where
+    F: Fn(&str) -> Option<u8>,
+{
+    let keyed = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index, key(line)?)))
+        .collect::<Vec<(usize, u8)>>();
+
+    if keyed.iter().any(|&(_, existing)| existing == day) {
+        return Err(format!("Day {} is already registered", day));
+    }
+
+    Ok(keyed
+        .iter()
+        .find(|&&(_, existing)| existing > day)
+        .map(|&(index, _)| index)
+        .or_else(|| keyed.last().map(|&(index, _)| index + 1)))
+}
+
+#[cfg(test)]
+mod scaffolding {
+    use super::*;
+
+    const REGISTRY: &str = r#"pub mod day_01;
+pub mod day_03;
+
+pub const DAYS: [Day; 2] = [
+    Day {
+        number: 1,
+        part_1: solve_part_1::<day_01::Modules>,
+        part_2: solve_part_2::<day_01::Modules>,
+        trace: None,
+        bench: bench::solution::<day_01::Modules>,
+        input: embedded!("day_01"),
+    },
+    Day {
+        number: 3,
+        part_1: solve_part_1::<day_03::Circuit>,
+        part_2: solve_part_2::<day_03::Circuit>,
+        trace: None,
+        bench: bench::solution::<day_03::Circuit>,
+        input: embedded!("day_03"),
+    },
+];
+"#;
+
+    fn entry(day: u8, name: &str) -> String {
+        format!(
+            "    Day {{
+        number: {0},
+        part_1: solve_part_1::<day_{0:02}::{1}>,
+        part_2: solve_part_2::<day_{0:02}::{1}>,
+        trace: None,
+        bench: bench::solution::<day_{0:02}::{1}>,
+        input: embedded!(\"day_{0:02}\"),
+    }},
+",
+            day, name
+        )
+    }
+
+    #[test]
+    fn registers_in_order() {
+        let registered = register(REGISTRY, 2, "Puzzle").unwrap();
+
+        assert!(registered.starts_with("pub mod day_01;\npub mod day_02;\npub mod day_03;\n"));
+        assert!(registered.contains("pub const DAYS: [Day; 3] = ["));
+        assert!(registered.contains(&format!(
+            "    }},\n{}    Day {{\n        number: 3,",
+            entry(2, "Puzzle")
+        )));
+
+        let appended = register(REGISTRY, 9, "Intcode").unwrap();
+        assert!(appended.contains("pub mod day_03;\npub mod day_09;\n"));
+        assert!(appended.ends_with(&format!("{}];\n", entry(9, "Intcode"))));
+
+        assert!(register(REGISTRY, 3, "Puzzle").is_err());
+    }
+
+    #[test]
+    fn lists_in_readme() {
+        let readme = [
+            "## Solutions",
+            readme::START,
+            "",
+            "| Day | Solution | Problem | Part 1 | Part 2 | Time |",
+            "| --: | -------- | ------- | ------ | ------ | ---: |",
+            &readme::row(1, "verified", "verified", None),
+            &readme::row(8, "verified", "verified", None),
+            "",
+            readme::END,
+        ]
+        .join("\n");
+
+        let listed = list(&readme, 9).unwrap();
+        let lines = listed.lines().collect::<Vec<&str>>();
+        assert_eq!(lines[7], readme::row(9, "unsolved", "unsolved", None));
+        assert_eq!(lines[8], "");
+
+        let listed = list(&readme, 2).unwrap();
+        assert_eq!(
+            listed.lines().nth(6),
+            Some(readme::row(2, "unsolved", "unsolved", None).as_str())
+        );
+
+        assert!(list(&readme, 8).is_err());
+        assert!(list("## Solutions\n", 9).is_err());
+    }
+
+    #[test]
+    fn arguments() {
+        let parse =
+            |args: &[&str]| parse(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>());
+
+        assert_eq!(parse(&["9"]), Ok((9, "Puzzle".to_string())));
+        assert_eq!(
+            parse(&["9", "--name", "Oxygen"]),
+            Ok((9, "Oxygen".to_string()))
+        );
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["26"]).is_err());
+        assert!(parse(&["9", "--name", "oxygen"]).is_err());
+    }
+
+    #[test]
+    fn template_names() {
+        let source = template(9, "Oxygen");
+
+        assert!(source.contains("pub struct Oxygen {}"));
+        assert!(source.contains("impl Solution for Oxygen {"));
+        assert!(source.contains("mod day_09 {"));
+        assert!(!source.contains("Puzzle"));
+    }
+}
@@ -0,0 +1,191 @@
+use advent_of_code_2019::answers::{Answers, ANSWERS_PATH};
+use advent_of_code_2019::bench::{Results, LATEST_PATH};
+use advent_of_code_2019::days::{Day, Solver, DAYS};
+use advent_of_code_2019::input::{Source, DEFAULT_DIR};
+use std::path::Path;
+use std::time::Duration;
+
+const USAGE: &str = "\
+Usage: aoc readme
+
+Regenerates the README's solutions table between its markers, solving both
+parts of every day on the inputs in src/inputs, ignoring AOC_INPUT_DIR, to
+check them against src/inputs/answers and taking timings from the latest
+aoc bench run.";
+
+pub const START: &str = "<!-- solutions:start -->";
+pub const END: &str = "<!-- solutions:end -->";
+
+/// Regenerates the table, returning the exit code.
+pub fn main(args: &[String]) -> i32 {
+    match args.first().map(String::as_str) {
+        Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            return 0;
+        }
+        Some(arg) => {
+            eprintln!("Unexpected argument '{}'\n\n{}", arg, USAGE);
+            return 2;
+        }
+        None => {}
+    }
+
+    let readme = Path::new(env!("CARGO_MANIFEST_DIR")).join("README.md");
+    match update(&readme) {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("{}", err);
+            1
+        }
+    }
+}
+
+fn update(readme: &Path) -> Result<(), String> {
+    let answers = Answers::load(ANSWERS_PATH)?;
+    let timings = match std::fs::read_to_string(LATEST_PATH) {
+        Ok(results) => results
+            .parse::<Results>()
+            .map_err(|err| format!("Invalid benchmark results {}: {}", LATEST_PATH, err))?,
+        Err(_) => Results::default(),
+    };
+
+    let rows = DAYS
+        .iter()
+        .map(|day| {
+            let (part_1, part_2) = statuses(day, &answers);
+            row(day.number, part_1, part_2, time(day.number, &timings))
+        })
+        .collect::<Vec<String>>();
+
+    let contents = std::fs::read_to_string(readme)
+        .map_err(|err| format!("Failed to read {}: {}", readme.display(), err))?;
+    let contents = replace_section(&contents, &table(&rows))?;
+
+    std::fs::write(readme, contents)
+        .map_err(|err| format!("Failed to write {}: {}", readme.display(), err))
+}
+
+/// Whether each part is solved and its answer matches the known answer.
+/// Only the repository's own inputs are used, whatever AOC_INPUT_DIR says,
+/// as the table describes them.
+fn statuses(day: &Day, answers: &Answers) -> (&'static str, &'static str) {
+    let source = Source::Path(DEFAULT_DIR.into());
+    let input = match source.read(day.number) {
+        Ok(input) => input,
+        Err(_) => return ("no input", "no input"),
+    };
+
+    let status = |part: u8, solver: Solver| match solver(&input) {
+        Ok(answer) => match answers.get(day.number, part, &source.name(day.number)) {
+            Some(expected) if expected == answer.to_string() => "verified",
+            Some(_) => "wrong",
+            None => "unverified",
+        },
+        Err(_) => "unsolved",
+    };
+
+    (status(1, day.part_1), status(2, day.part_2))
+}
+
+/// The sum of the median times to parse and solve both parts, if all of
+/// them were benchmarked.
+fn time(day: u8, timings: &Results) -> Option<Duration> {
+    ["parse", "part_1", "part_2"]
+        .iter()
+        .map(|name| {
+            let name = format!("day_{:02}/{}", day, name);
+            timings
+                .measurements
+                .iter()
+                .find(|measurement| measurement.name == name)
+                .map(|measurement| measurement.stats.median)
+        })
+        .sum()
+}
+
+pub fn row(day: u8, part_1: &str, part_2: &str, time: Option<Duration>) -> String {
+    format!(
+        "| {} | [day_{:02}.rs](src/days/day_{:02}.rs) | [day {}](https://adventofcode.com/2019/day/{}) | {} | {} | {} |",
+        day,
+        day,
+        day,
+        day,
+        day,
+        part_1,
+        part_2,
+        time.map_or("-".to_string(), |time| format!("{:.2?}", time))
+    )
+}
+
+fn table(rows: &[String]) -> String {
+    let mut lines = vec![
+        "| Day | Solution | Problem | Part 1 | Part 2 | Time |".to_string(),
+        "| --: | -------- | ------- | ------ | ------ | ---: |".to_string(),
+    ];
+    lines.extend(rows.iter().cloned());
+
+    lines.join("\n")
+}
+
+/// Replaces everything between the markers with `table`.
+fn replace_section(readme: &str, table: &str) -> Result<String, String> {
+    let start = readme
+        .find(START)
+        .ok_or_else(|| format!("The README has no {} marker", START))?
+        + START.len();
+    let end = readme[start..]
+        .find(END)
+        .ok_or_else(|| format!("The README has no {} marker after {}", END, START))?
+        + start;
+
+    Ok(format!(
+        "{}\n\n{}\n\n{}",
+        &readme[..start],
+        table,
+        &readme[end..]
+    ))
+}
+
+#[cfg(test)]
+mod solutions_table {
+    use super::*;
+
+    #[test]
+    fn rows() {
+        assert_eq!(
+            row(7, "verified", "wrong", Some(Duration::from_micros(1500))),
+            "| 7 | [day_07.rs](src/days/day_07.rs) | [day 7](https://adventofcode.com/2019/day/7) \
+             | verified | wrong | 1.50ms |"
+        );
+        assert!(row(9, "unsolved", "unsolved", None).ends_with("| unsolved | unsolved | - |"));
+    }
+
+    #[test]
+    fn replaces_between_markers() {
+        let readme = format!("# Title\n\n{}\nold\n{}\n\n## Next\n", START, END);
+
+        assert_eq!(
+            replace_section(&readme, "new").unwrap(),
+            format!("# Title\n\n{}\n\nnew\n\n{}\n\n## Next\n", START, END)
+        );
+        assert_eq!(
+            replace_section(&replace_section(&readme, "new").unwrap(), "new"),
+            replace_section(&readme, "new")
+        );
+        assert!(replace_section("# Title\n", "new").is_err());
+        assert!(replace_section(&format!("{}\n", START), "new").is_err());
+    }
+
+    #[test]
+    fn timings() {
+        let timings = "day_01/parse 1 10 10 10 10\n\
+                       day_01/part_1 1 20 20 20 20\n\
+                       day_01/part_2 1 30 30 30 30\n\
+                       day_02/parse 1 10 10 10 10\n"
+            .parse()
+            .unwrap();
+
+        assert_eq!(time(1, &timings), Some(Duration::from_nanos(60)));
+        assert_eq!(time(2, &timings), None);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::parse::{self, ParseError};
+use crate::{Answer, Error, Solution};
+
+#[derive(Debug)]
+pub struct Module {
+    mass: u32,
+}
+
+impl Module {
+    pub fn new(mass: u32) -> Module {
+        Module { mass }
+    }
+
+    fn calculate_fuel(mass: u32) -> i64 {
+        (mass as i64) / 3 - 2
+    }
+
+    /// Fuel needed to launch the module itself.
+    pub fn part_1(&self) -> u32 {
+        Self::calculate_fuel(self.mass) as u32
+    }
+
+    /// Fuel needed to launch the module, including the fuel's own mass.
+    pub fn part_2(&self) -> u32 {
+        let mut total: u32 = 0;
+        let mut tmp: i64 = Self::calculate_fuel(self.mass);
+        while tmp > 0 {
+            total += tmp as u32;
+            tmp = Self::calculate_fuel(tmp as u32);
+        }
+        total
+    }
+}
+
+impl std::str::FromStr for Module {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Module::new(parse::value(s, s)?))
+    }
+}
+
+#[derive(Debug)]
+pub struct Modules {
+    modules: Vec<Module>,
+}
+
+impl Modules {
+    pub fn from_vec(modules: Vec<Module>) -> Modules {
+        Modules { modules }
+    }
+}
+
+impl Solution for Modules {
+    type Err = Error;
+
+    fn parse(input: &str) -> Result<Self, Self::Err> {
+        input
+            .parse()
+            .map_err(|err: ParseError| Error::Parse(err.on_day(1)))
+    }
+
+    fn part_1(&self) -> Result<Answer, Self::Err> {
+        Ok(self
+            .modules
+            .iter()
+            .map(|module| module.part_1())
+            .sum::<u32>()
+            .into())
+    }
+
+    fn part_2(&self) -> Result<Answer, Self::Err> {
+        Ok(self
+            .modules
+            .iter()
+            .map(|module| module.part_2())
+            .sum::<u32>()
+            .into())
+    }
+}
+
+impl std::str::FromStr for Modules {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tmp: Result<Vec<Module>, Self::Err> = s
+            .lines()
+            .map(|line| line.parse::<Module>().map_err(|err| err.within(s, line)))
+            .collect();
+        Ok(Modules::from_vec(tmp?))
+    }
+}
+
+#[cfg(test)]
+mod day_01 {
+    use super::*;
+    #[test]
+    fn test_part_1() {
+        assert_eq!(Module::new(12).part_1(), 2);
+        assert_eq!(Module::new(14).part_1(), 2);
+        assert_eq!(Module::new(1969).part_1(), 654);
+        assert_eq!(Module::new(100_756).part_1(), 33583);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(Module::new(14).part_2(), 2);
+        assert_eq!(Module::new(1969).part_2(), 966);
+        assert_eq!(Module::new(100_756).part_2(), 50346);
+    }
+
+    #[test]
+    fn test_module_parse() {
+        assert_eq!("12".parse::<Module>().unwrap().mass, 12);
+        assert_eq!("1234".parse::<Module>().unwrap().mass, 1234);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Modules::parse("12\n14\n\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to parse input: day 1 input 3:1: cannot parse integer from empty string at \"\""
+        );
+
+        let err = "12\n1x4".parse::<Modules>().unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "1x4"));
+    }
+}
@@ -0,0 +1,55 @@
+use crate::parse;
+use crate::{Answer, Error, Solution};
+use intcode::ProgramState;
+
+/// The gravity assist program, which is run with a noun and a verb.
+pub struct GravityAssist {
+    program: intcode::Program,
+}
+
+impl Solution for GravityAssist {
+    type Err = Error;
+
+    fn parse(input: &str) -> Result<Self, Self::Err> {
+        let program = parse::program(input).map_err(|err| Error::Parse(err.on_day(2)))?;
+
+        Ok(GravityAssist { program })
+    }
+
+    fn part_1(&self) -> Result<Answer, Self::Err> {
+        Ok(self.run(12, 2)?.into())
+    }
+
+    // brute force algorithm
+    fn part_2(&self) -> Result<Answer, Self::Err> {
+        let target: i32 = 19_690_720;
+
+        for noun in 0..99 {
+            for verb in 0..99 {
+                if self.run(noun, verb)? == target {
+                    return Ok((100 * noun + verb).into());
+                }
+            }
+        }
+
+        Err(Error::NoAnswer(format!(
+            "Failed to find a noun, verb pair that results in {}",
+            target
+        )))
+    }
+}
+
+impl GravityAssist {
+    /// Runs the program with `noun` and `verb`, returning the value it leaves
+    /// at address 0.
+    pub fn run(&self, noun: i32, verb: i32) -> Result<i32, Error> {
+        let mut program = self.program.clone();
+        program.set_noun(noun);
+        program.set_verb(verb);
+
+        match program.execute() {
+            ProgramState::Complete => Ok(program.get_zero()),
+            state => Err(Error::Program(state)),
+        }
+    }
+}
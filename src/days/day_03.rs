@@ -1,16 +1,8 @@
-fn main() {
-    let (part_1, part_2) = std::fs::read_to_string("src/inputs/day_03")
-        .expect("Failed to read input file")
-        .parse::<Circuit>()
-        .unwrap()
-        .run()
-        .unwrap();
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
-}
+use crate::geometry::{Direction, Point};
+use crate::parse::{self, ParseError};
+use crate::{Answer, Error, Solution};
 
-struct Circuit {
+pub struct Circuit {
     wires: (Wire, Wire),
 }
 
@@ -24,26 +16,12 @@ struct Segment {
     steps: u64,
 }
 
-#[derive(Clone)]
-enum Direction {
-    UP,
-    DOWN,
-    LEFT,
-    RIGHT,
-}
-
 #[derive(Clone)]
 struct Translation {
     direction: Direction,
     distance: u64,
 }
 
-#[derive(Clone)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
 struct Intersection {
     point: Point,
     steps: u64,
@@ -78,31 +56,22 @@ impl Segment {
         }
     }
 
-    fn endpoints(&self) -> (i32, i32) {
-        match self.translation.direction {
-            Direction::UP => (
-                self.point.y,
-                self.point.y + self.translation.distance as i32,
-            ),
-            Direction::DOWN => (
-                self.point.y - self.translation.distance as i32,
-                self.point.y,
-            ),
-            Direction::LEFT => (
-                self.point.x - self.translation.distance as i32,
-                self.point.x,
-            ),
-            Direction::RIGHT => (
-                self.point.x,
-                self.point.x + self.translation.distance as i32,
-            ),
+    fn endpoints(&self) -> (i64, i64) {
+        let end = self
+            .point
+            .step(self.translation.direction, self.translation.distance as i64);
+        if self.translation.direction.is_vertical() {
+            (self.point.y.min(end.y), self.point.y.max(end.y))
+        } else {
+            (self.point.x.min(end.x), self.point.x.max(end.x))
         }
     }
 
     fn orientation(&self) -> Orientation {
-        match self.translation.direction {
-            Direction::UP | Direction::DOWN => Orientation::VERTICAL,
-            Direction::LEFT | Direction::RIGHT => Orientation::HORIZONTAL,
+        if self.translation.direction.is_vertical() {
+            Orientation::VERTICAL
+        } else {
+            Orientation::HORIZONTAL
         }
     }
 
@@ -115,8 +84,8 @@ impl Segment {
                 let y = self.point.y;
 
                 if (x_min..=x_max).contains(&x) && (y_min..=y_max).contains(&y) {
-                    let x_steps = self.steps + (self.point.x - x).abs() as u64;
-                    let y_steps = other.steps + (other.point.y - y).abs() as u64;
+                    let x_steps = self.steps + (self.point.x - x).unsigned_abs();
+                    let y_steps = other.steps + (other.point.y - y).unsigned_abs();
                     let steps = x_steps + y_steps;
                     Some(Intersection::new(Point::new(x, y), steps))
                 } else {
@@ -130,8 +99,8 @@ impl Segment {
                 let y = other.point.y;
 
                 if (x_min..=x_max).contains(&x) && (y_min..=y_max).contains(&y) {
-                    let x_steps = other.steps + (other.point.x - x).abs() as u64;
-                    let y_steps = self.steps + (self.point.y - y).abs() as u64;
+                    let x_steps = other.steps + (other.point.x - x).unsigned_abs();
+                    let y_steps = self.steps + (self.point.y - y).unsigned_abs();
                     let steps = x_steps + y_steps;
                     Some(Intersection::new(Point::new(x, y), steps))
                 } else {
@@ -143,43 +112,38 @@ impl Segment {
     }
 }
 
-impl Point {
-    fn origin() -> Point {
-        Point::new(0, 0)
-    }
-
-    fn new(x: i32, y: i32) -> Point {
-        Point { x, y }
-    }
+impl Solution for Circuit {
+    type Err = Error;
 
-    fn manhattan_distance(&self) -> u64 {
-        self.x.abs() as u64 + self.y.abs() as u64
+    fn parse(input: &str) -> Result<Self, Self::Err> {
+        input
+            .parse()
+            .map_err(|err: ParseError| Error::Parse(err.on_day(3)))
     }
 
-    fn is_origin(&self) -> bool {
-        self.x == 0 && self.y == 0
+    fn part_1(&self) -> Result<Answer, Self::Err> {
+        let (part_1, _) = self.run().map_err(Error::NoAnswer)?;
+        Ok(part_1.into())
     }
 
-    fn translate(&mut self, translation: &Translation) {
-        match translation.direction {
-            Direction::UP => self.y += translation.distance as i32,
-            Direction::DOWN => self.y -= translation.distance as i32,
-            Direction::LEFT => self.x -= translation.distance as i32,
-            Direction::RIGHT => self.x += translation.distance as i32,
-        }
+    fn part_2(&self) -> Result<Answer, Self::Err> {
+        let (_, part_2) = self.run().map_err(Error::NoAnswer)?;
+        Ok(part_2.into())
     }
 }
 
 impl Circuit {
-    fn run(&self) -> Result<(u64, u64), String> {
+    /// Finds the intersection closest to the origin, and the one the wires
+    /// reach in the fewest combined steps.
+    pub fn run(&self) -> Result<(u64, u64), String> {
         let mut part_1: Option<u64> = None;
         let mut part_2: Option<u64> = None;
 
         for s0 in self.wires.0.segments.iter() {
             for s1 in self.wires.1.segments.iter() {
                 if let Some(intersect) = s0.intersect(s1) {
-                    if !intersect.point.is_origin() {
-                        set_min(&mut part_1, intersect.point.manhattan_distance());
+                    if intersect.point != Point::ORIGIN {
+                        set_min(&mut part_1, Point::ORIGIN.manhattan(intersect.point));
                         set_min(&mut part_2, intersect.steps);
                     }
                 }
@@ -203,20 +167,24 @@ fn set_min(maybe_val: &mut Option<u64>, new_val: u64) {
 }
 
 impl std::str::FromStr for Circuit {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut lines = s.lines();
-        let line_1 = lines.next().ok_or("First line of input missing")?;
-        let line_2 = lines.next().ok_or("Second line of input missing")?;
-
-        match lines.next() {
-            Some(_) => Err("More than two lines of input found".to_string()),
-            None => Ok(()),
-        }?;
+        let line_1 = lines
+            .next()
+            .ok_or_else(|| ParseError::at_end(s, "first wire missing"))?;
+        let line_2 = lines
+            .next()
+            .ok_or_else(|| ParseError::at_end(s, "second wire missing"))?;
+
+        if let Some(extra) = lines.next() {
+            return Err(ParseError::at(s, extra, "expected only two wires"));
+        }
 
-        let wire_1 = line_1.parse::<Wire>()?;
-        let wire_2 = line_2.parse::<Wire>()?;
+        let wire = |line: &str| line.parse::<Wire>().map_err(|err| err.within(s, line));
+        let wire_1 = wire(line_1)?;
+        let wire_2 = wire(line_2)?;
 
         Ok(Circuit {
             wires: (wire_1, wire_2),
@@ -225,27 +193,27 @@ impl std::str::FromStr for Circuit {
 }
 
 impl std::str::FromStr for Wire {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let translations = s
             .split(',')
-            .map(|word| word.parse::<Translation>())
+            .map(|word| {
+                word.parse::<Translation>()
+                    .map_err(|err| err.within(s, word))
+            })
             .collect::<Result<Vec<Translation>, Self::Err>>()?;
 
-        let mut current_origin = Point::origin();
+        let mut current_origin = Point::ORIGIN;
         let mut current_steps: u64 = 0;
 
         let segments = translations
             .iter()
             .map(|translation| {
-                let segment = Segment::new(
-                    current_origin.clone(),
-                    (*translation).clone(),
-                    current_steps,
-                );
+                let segment = Segment::new(current_origin, (*translation).clone(), current_steps);
 
-                current_origin.translate(translation);
+                current_origin =
+                    current_origin.step(translation.direction, translation.distance as i64);
                 current_steps += translation.distance;
                 segment
             })
@@ -256,20 +224,18 @@ impl std::str::FromStr for Wire {
 }
 
 impl std::str::FromStr for Translation {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut c = s.chars();
-        let direction = match c.next() {
-            Some('U') => Ok(Direction::UP),
-            Some('D') => Ok(Direction::DOWN),
-            Some('L') => Ok(Direction::LEFT),
-            Some('R') => Ok(Direction::RIGHT),
-            Some(unknown) => Err(format!("Bad Translation direction {}", unknown)),
-            None => Err("No direction char found".to_string()),
-        }?;
-
-        let distance = c.as_str().parse::<u64>().map_err(|err| err.to_string())?;
+        let split = s
+            .chars()
+            .next()
+            .ok_or_else(|| ParseError::at(s, s, "expected a direction and distance"))?
+            .len_utf8();
+        let direction = s[..split]
+            .parse::<Direction>()
+            .map_err(|err| err.within(s, &s[..split]))?;
+        let distance = parse::value::<u64>(s, &s[split..])?;
 
         Ok(Translation::new(direction, distance))
     }
@@ -1,23 +1,64 @@
-fn main() {
-    let valid_1 = (265_275..=781_584)
-        .map(Digits::new)
-        .filter(Digits::is_valid_password_1)
-        .collect::<Vec<Digits>>();
-
-    let part_1 = valid_1.len();
-    let part_2 = valid_1
-        .iter()
-        .filter(|digits| digits.has_double_strict())
-        .count();
-
-    println!("Part 1: {}", part_1);
-    println!("Part 2: {}", part_2);
+use crate::parse::{self, ParseError};
+use crate::{Answer, Error, Solution};
+use std::ops::RangeInclusive;
+
+/// The range of numbers the password lies in.
+pub struct Passwords {
+    range: RangeInclusive<usize>,
+}
+
+impl Passwords {
+    /// The passwords in the range that satisfy the rules of part 1.
+    pub fn valid(&self) -> Vec<Digits> {
+        self.range
+            .clone()
+            .map(Digits::new)
+            .filter(Digits::is_valid_password_1)
+            .collect()
+    }
+}
+
+impl Solution for Passwords {
+    type Err = Error;
+
+    fn parse(input: &str) -> Result<Self, Self::Err> {
+        let fail = |err: ParseError| Error::Parse(err.on_day(4));
+        let range = input.trim();
+        let mut bounds = range
+            .split('-')
+            .map(|bound| parse::value::<usize>(input, bound).map_err(fail));
+
+        match (bounds.next(), bounds.next(), bounds.next()) {
+            (Some(low), Some(high), None) => Ok(Passwords {
+                range: low?..=high?,
+            }),
+            _ => Err(fail(ParseError::at(
+                input,
+                range,
+                "expected a range such as 1-9",
+            ))),
+        }
+    }
+
+    fn part_1(&self) -> Result<Answer, Self::Err> {
+        Ok(self.valid().len().into())
+    }
+
+    fn part_2(&self) -> Result<Answer, Self::Err> {
+        let part_2 = self
+            .valid()
+            .iter()
+            .filter(|digits| digits.has_double_strict())
+            .count();
+
+        Ok(part_2.into())
+    }
 }
 
-struct Digits([usize; 6]);
+pub struct Digits([usize; 6]);
 
 impl Digits {
-    fn new(x: usize) -> Digits {
+    pub fn new(x: usize) -> Digits {
         let digits = [
             ((x / 100_000) % 10),
             ((x / 10_000) % 10),
@@ -30,7 +71,7 @@ impl Digits {
         Digits(digits)
     }
 
-    fn has_double(&self) -> bool {
+    pub fn has_double(&self) -> bool {
         for i in 1..6 {
             if self.0[i] == self.0[i - 1] {
                 return true;
@@ -39,7 +80,7 @@ impl Digits {
         false
     }
 
-    fn has_double_strict(&self) -> bool {
+    pub fn has_double_strict(&self) -> bool {
         let mut count = 0;
 
         for i in 1..6 {
@@ -57,7 +98,7 @@ impl Digits {
         count == 1
     }
 
-    fn is_increasing(&self) -> bool {
+    pub fn is_increasing(&self) -> bool {
         for i in 1..6 {
             if self.0[i] < self.0[i - 1] {
                 return false;
@@ -66,7 +107,7 @@ impl Digits {
         true
     }
 
-    fn is_valid_password_1(&self) -> bool {
+    pub fn is_valid_password_1(&self) -> bool {
         self.is_increasing() && self.has_double()
     }
 }
@@ -0,0 +1,44 @@
+use crate::parse;
+use crate::{Answer, Error, Solution};
+use intcode::ProgramState;
+
+/// The thermal environment supervision terminal's diagnostic program.
+pub struct Diagnostic {
+    program: intcode::Program,
+}
+
+impl Diagnostic {
+    /// Runs the diagnostic for the system with `system_id`, returning its
+    /// diagnostic code.
+    pub fn run(&self, system_id: i32) -> Result<i32, Error> {
+        let mut program = self.program.clone();
+
+        program.push_back_input(system_id);
+        match program.execute() {
+            ProgramState::Complete => {}
+            state => return Err(Error::Program(state)),
+        }
+
+        program
+            .pop_latest_output()
+            .ok_or_else(|| Error::NoAnswer("Diagnostic produced no output".to_string()))
+    }
+}
+
+impl Solution for Diagnostic {
+    type Err = Error;
+
+    fn parse(input: &str) -> Result<Self, Self::Err> {
+        let program = parse::program(input).map_err(|err| Error::Parse(err.on_day(5)))?;
+
+        Ok(Diagnostic { program })
+    }
+
+    fn part_1(&self) -> Result<Answer, Self::Err> {
+        Ok(self.run(1)?.into())
+    }
+
+    fn part_2(&self) -> Result<Answer, Self::Err> {
+        Ok(self.run(5)?.into())
+    }
+}
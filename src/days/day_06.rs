@@ -0,0 +1,155 @@
+use crate::graph::{self, Graph};
+use crate::parse::ParseError;
+use crate::{Answer, Error, Solution};
+use std::convert::From;
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+pub struct ObjectID(String);
+
+impl From<String> for ObjectID {
+    fn from(s: String) -> Self {
+        ObjectID(s)
+    }
+}
+
+impl From<&str> for ObjectID {
+    fn from(s: &str) -> Self {
+        ObjectID(s.to_string())
+    }
+}
+
+/// Each object has an edge to the object it orbits.
+pub struct System {
+    orbits: Graph<ObjectID>,
+}
+
+impl System {
+    fn new() -> System {
+        System {
+            orbits: Graph::directed(),
+        }
+    }
+
+    /// The objects `object` orbits, directly or indirectly, innermost last.
+    pub fn origin_path(&self, object: &ObjectID) -> Vec<ObjectID> {
+        let mut path: Vec<ObjectID> = Vec::new();
+        let mut current = object;
+        while let Some(next) = self.orbits.neighbours(current).next() {
+            path.push((*next).clone());
+            current = next;
+        }
+
+        path
+    }
+
+    /// Number of orbital transfers needed to move from the object `a` orbits
+    /// to the object `b` orbits, if they share a center.
+    pub fn transfer_distance(&self, a: &ObjectID, b: &ObjectID) -> Option<usize> {
+        let distances = graph::bfs(a.clone(), |object| self.orbits.adjacent(object).cloned());
+        distances.get(b)?.checked_sub(2)
+    }
+
+    pub fn total_orbits(&self) -> usize {
+        self.orbits
+            .nodes()
+            .map(|object| self.origin_path(object).len())
+            .sum()
+    }
+}
+
+impl Solution for System {
+    type Err = Error;
+
+    fn parse(input: &str) -> Result<Self, Self::Err> {
+        input
+            .parse()
+            .map_err(|err: ParseError| Error::Parse(err.on_day(6)))
+    }
+
+    fn part_1(&self) -> Result<Answer, Self::Err> {
+        Ok(self.total_orbits().into())
+    }
+
+    fn part_2(&self) -> Result<Answer, Self::Err> {
+        let distance = self
+            .transfer_distance(&ObjectID::from("YOU"), &ObjectID::from("SAN"))
+            .ok_or_else(|| Error::NoAnswer("YOU and SAN orbit nothing in common".to_string()))?;
+        Ok(distance.into())
+    }
+}
+
+impl std::str::FromStr for System {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut system = System::new();
+        for line in s.lines() {
+            let (parent, child) = line
+                .split_once(')')
+                .ok_or_else(|| ParseError::at(s, line, "expected an orbit such as A)B"))?;
+
+            if system.orbits.neighbours(&child.into()).next().is_some() {
+                return Err(ParseError::at(s, child, "object already orbits another"));
+            }
+            system.orbits.add_edge(child.into(), parent.into());
+        }
+
+        Ok(system)
+    }
+}
+
+#[cfg(test)]
+mod day_06 {
+    use super::*;
+
+    #[test]
+    fn orbit_count() {
+        let output = "COM)B\n\
+                      B)C\n\
+                      C)D\n\
+                      D)E\n\
+                      E)F\n\
+                      B)G\n\
+                      G)H\n\
+                      D)I\n\
+                      E)J\n\
+                      J)K\n\
+                      K)L"
+        .parse::<System>()
+        .unwrap()
+        .total_orbits();
+
+        assert_eq!(output, 42);
+    }
+
+    #[test]
+    fn path() {
+        let output = "COM)B\n\
+                      B)C\n\
+                      C)D\n\
+                      D)E\n\
+                      E)F\n\
+                      B)G\n\
+                      G)H\n\
+                      D)I\n\
+                      E)J\n\
+                      J)K\n\
+                      K)L\n\
+                      K)YOU\n\
+                      I)SAN"
+            .parse::<System>()
+            .unwrap()
+            .transfer_distance(&ObjectID::from("YOU"), &ObjectID::from("SAN"));
+
+        assert_eq!(output, Some(4));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = "COM)B\nBC".parse::<System>().err().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "BC"));
+
+        let err = "COM)B\nCOM)B".parse::<System>().err().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 5, "B"));
+    }
+}
@@ -0,0 +1,505 @@
+use crate::parse::{self, ParseError};
+use crate::{Answer, Solution};
+use intcode::pipeline::Pipeline;
+use intcode::scheduler::SchedulerError;
+use intcode::search::{search, Candidate, Search};
+use intcode::ProgramState;
+
+pub struct AmplifierController {
+    code: intcode::Program,
+}
+
+impl Solution for AmplifierController {
+    type Err = AmplifierError;
+
+    fn parse(input: &str) -> Result<Self, Self::Err> {
+        let code =
+            parse::program(input).map_err(|err| AmplifierError::InvalidProgram(err.on_day(7)))?;
+
+        Ok(AmplifierController::new(code))
+    }
+
+    fn part_1(&self) -> Result<Answer, Self::Err> {
+        Ok(self.best_sequence()?.signal.into())
+    }
+
+    fn part_2(&self) -> Result<Answer, Self::Err> {
+        Ok(self.best_feedback_loop()?.signal.into())
+    }
+}
+
+/// Renders how the signal passed through the amplifiers for the best phase
+/// settings of both parts.
+pub fn trace(input: &str) -> Result<String, String> {
+    AmplifierController::parse(input)
+        .and_then(|amp_control| amp_control.trace())
+        .map_err(|err| err.to_string())
+}
+
+impl AmplifierController {
+    pub fn new(code: intcode::Program) -> AmplifierController {
+        AmplifierController { code }
+    }
+
+    /// The phase settings giving the strongest signal from a chain of five
+    /// amplifiers.
+    pub fn best_sequence(&self) -> Result<Candidate<i32, i32>, AmplifierError> {
+        Self::best(search(&[0, 1, 2, 3, 4], 5, 1, |phases| {
+            self.run_sequence(phases)
+        }))
+    }
+
+    /// The phase settings giving the strongest signal from five amplifiers
+    /// in a feedback loop.
+    pub fn best_feedback_loop(&self) -> Result<Candidate<i32, i32>, AmplifierError> {
+        Self::best(search(&[5, 6, 7, 8, 9], 5, 1, |phases| {
+            self.run_feedback_loop(phases)
+        }))
+    }
+
+    /// Renders the traces of the best phase settings of both parts.
+    pub fn trace(&self) -> Result<String, AmplifierError> {
+        let part_1 = self.best_sequence()?;
+        let part_2 = self.best_feedback_loop()?;
+
+        let (_, trace_1) = self.trace_sequence(&part_1.phases);
+        let (_, trace_2) = self.trace_feedback_loop(&part_2.phases);
+
+        Ok(format!(
+            "Part 1 phases {:?}:\n{}\n\nPart 2 phases {:?}:\n{}",
+            part_1.phases,
+            trace_1.render(),
+            part_2.phases,
+            trace_2.render()
+        ))
+    }
+
+    pub fn run_sequence(&self, phases: &[i32]) -> Result<i32, AmplifierError> {
+        Self::run(Pipeline::chain(&self.code, phases), phases.len(), None)
+    }
+
+    pub fn run_feedback_loop(&self, phases: &[i32]) -> Result<i32, AmplifierError> {
+        Self::run(Pipeline::ring(&self.code, phases), phases.len(), None)
+    }
+
+    pub fn trace_sequence(&self, phases: &[i32]) -> (Result<i32, AmplifierError>, Trace) {
+        let mut trace = Trace::new(phases.len(), false);
+        let pipeline = Pipeline::chain(&self.code, phases);
+        let result = Self::run(pipeline, phases.len(), Some(&mut trace));
+
+        (result, trace)
+    }
+
+    pub fn trace_feedback_loop(&self, phases: &[i32]) -> (Result<i32, AmplifierError>, Trace) {
+        let mut trace = Trace::new(phases.len(), true);
+        let pipeline = Pipeline::ring(&self.code, phases);
+        let result = Self::run(pipeline, phases.len(), Some(&mut trace));
+
+        (result, trace)
+    }
+
+    /// The best candidate, or if every phase setting failed the first error.
+    fn best(
+        mut search: Search<i32, i32, AmplifierError>,
+    ) -> Result<Candidate<i32, i32>, AmplifierError> {
+        if search.top.is_empty() {
+            return Err(search
+                .failures
+                .into_iter()
+                .next()
+                .map_or(AmplifierError::NoAmplifiers, |failure| failure.error));
+        }
+
+        Ok(search.top.remove(0))
+    }
+
+    fn run(
+        mut pipeline: Pipeline,
+        amplifiers: usize,
+        mut trace: Option<&mut Trace>,
+    ) -> Result<i32, AmplifierError> {
+        pipeline
+            .push_input(0, 0)
+            .map_err(|_| AmplifierError::NoAmplifiers)?;
+
+        let mut rounds = vec![1; amplifiers];
+        let output = pipeline.run_observed(|amplifier, state| {
+            if let Some(trace) = trace.as_mut() {
+                trace.record(amplifier, state);
+            }
+            if let ProgramState::Output(_) = state {
+                rounds[amplifier] += 1;
+            }
+        });
+
+        let output = output.map_err(|err| match err {
+            SchedulerError::Failed { program, error } => AmplifierError::Stopped {
+                amplifier: program,
+                round: rounds[program],
+                state: ProgramState::Err(error),
+            },
+            SchedulerError::Starved { program } => AmplifierError::Stopped {
+                amplifier: program,
+                round: rounds[program],
+                state: ProgramState::RequiresInput,
+            },
+            SchedulerError::Deadlock { cycle } => AmplifierError::Deadlock {
+                round: rounds[cycle[0]],
+                amplifiers: cycle,
+            },
+        })?;
+
+        output.signal.ok_or(AmplifierError::NoOutput {
+            amplifier: amplifiers - 1,
+        })
+    }
+}
+
+/// Why a set of amplifiers did not produce a signal. Amplifiers are numbered
+/// from zero and rounds from one, with an amplifier's n-th round ending once
+/// it passes on its n-th value.
+#[derive(Debug, PartialEq)]
+pub enum AmplifierError {
+    /// The amplifier halted in `state` during `round`, either with an error
+    /// or waiting for input that no other amplifier can provide.
+    Stopped {
+        amplifier: usize,
+        round: usize,
+        state: ProgramState,
+    },
+    /// The amplifiers are each waiting for the next one's output, the last
+    /// for the first's. `round` is the round the first of them is stuck in.
+    Deadlock {
+        amplifiers: Vec<usize>,
+        round: usize,
+    },
+    /// The last amplifier completed without passing on a signal.
+    NoOutput {
+        amplifier: usize,
+    },
+    NoAmplifiers,
+    /// The amplifier controller software could not be parsed.
+    InvalidProgram(ParseError),
+}
+
+impl std::fmt::Display for AmplifierError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AmplifierError::Stopped {
+                amplifier,
+                round,
+                state,
+            } => match state {
+                ProgramState::Err(err) => write!(
+                    f,
+                    "Amplifier {} failed in round {}: {}",
+                    name(*amplifier),
+                    round,
+                    err
+                ),
+                ProgramState::RequiresInput => write!(
+                    f,
+                    "Amplifier {} is waiting for input in round {} that will never arrive",
+                    name(*amplifier),
+                    round
+                ),
+                state => write!(
+                    f,
+                    "Amplifier {} stopped in round {} with {:?}",
+                    name(*amplifier),
+                    round,
+                    state
+                ),
+            },
+            AmplifierError::Deadlock { amplifiers, round } => write!(
+                f,
+                "Amplifiers {} are waiting on each other in round {}",
+                amplifiers
+                    .iter()
+                    .chain(amplifiers.first())
+                    .map(|&amplifier| name(amplifier))
+                    .collect::<Vec<String>>()
+                    .join(" -> "),
+                round
+            ),
+            AmplifierError::NoOutput { amplifier } => {
+                write!(f, "Amplifier {} has no output", name(*amplifier))
+            }
+            AmplifierError::NoAmplifiers => write!(f, "There are no amplifiers"),
+            AmplifierError::InvalidProgram(err) => write!(f, "Failed to parse input: {}", err),
+        }
+    }
+}
+
+/// The puzzle's letters for the first 26 amplifiers, then their numbers.
+fn name(amplifier: usize) -> String {
+    match amplifier {
+        0..=25 => ((b'A' + amplifier as u8) as char).to_string(),
+        _ => format!("#{}", amplifier),
+    }
+}
+
+/// Every state the amplifiers paused in, grouped into rounds. An amplifier's
+/// n-th round ends with the n-th value it passes on.
+#[derive(Debug)]
+pub struct Trace {
+    feedback: bool,
+    outputs: Vec<usize>,
+    rounds: Vec<Vec<Vec<ProgramState>>>,
+}
+
+impl Trace {
+    fn new(amplifiers: usize, feedback: bool) -> Trace {
+        Trace {
+            feedback,
+            outputs: vec![0; amplifiers],
+            rounds: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, amplifier: usize, state: &ProgramState) {
+        let round = self.outputs[amplifier];
+        while self.rounds.len() <= round {
+            self.rounds.push(vec![Vec::new(); self.outputs.len()]);
+        }
+
+        self.rounds[round][amplifier].push(state.clone());
+        if let ProgramState::Output(_) = state {
+            self.outputs[amplifier] += 1;
+        }
+    }
+
+    /// Renders the trace as a table with one row per round and one column
+    /// per amplifier, headed by where its outputs are sent.
+    pub fn render(&self) -> String {
+        let amplifiers = self.outputs.len();
+
+        let mut header = vec!["Round".to_string()];
+        header.extend((0..amplifiers).map(|amplifier| {
+            let target = match amplifier + 1 {
+                next if next < amplifiers => name(next),
+                _ if self.feedback => format!("{} + out", name(0)),
+                _ => "out".to_string(),
+            };
+            format!("{} -> {}", name(amplifier), target)
+        }));
+
+        let mut rows = vec![header];
+        rows.extend(self.rounds.iter().enumerate().map(|(round, states)| {
+            let mut row = vec![(round + 1).to_string()];
+            row.extend(states.iter().map(|states| {
+                states
+                    .iter()
+                    .map(|state| match state {
+                        ProgramState::Output(value) => value.to_string(),
+                        ProgramState::RequiresInput => "waiting".to_string(),
+                        ProgramState::Complete => "complete".to_string(),
+                        ProgramState::Err(err) => format!("error: {}", err),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            }));
+            row
+        }));
+
+        let widths = (0..=amplifiers)
+            .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap())
+            .collect::<Vec<usize>>();
+        let separator = widths
+            .iter()
+            .map(|&width| "-".repeat(width))
+            .collect::<Vec<String>>()
+            .join("-+-");
+
+        let mut lines = rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(widths.iter())
+                    .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+                    .collect::<Vec<String>>()
+                    .join(" | ")
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<String>>();
+        lines.insert(1, separator);
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod day_07 {
+    use super::*;
+
+    fn test_sequence(code_str: &str, input_sequence: &[i32], output: i32) {
+        let code = code_str.parse::<intcode::Program>().unwrap();
+        let uut = AmplifierController::new(code);
+
+        assert_eq!(uut.run_sequence(input_sequence).unwrap(), output);
+    }
+
+    fn test_feedback(code_str: &str, input_sequence: &[i32], output: i32) {
+        let code = code_str.parse::<intcode::Program>().unwrap();
+        let uut = AmplifierController::new(code);
+
+        assert_eq!(uut.run_feedback_loop(input_sequence).unwrap(), output);
+    }
+
+    #[test]
+    fn part_1_1() {
+        test_sequence(
+            "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0",
+            &[4, 3, 2, 1, 0],
+            43210,
+        );
+    }
+
+    #[test]
+    fn part_1_2() {
+        test_sequence(
+            "3,23,3,24,1002,24,10,24,1002,23,-1,23,\
+             101,5,23,23,1,24,23,23,4,23,99,0,0",
+            &[0, 1, 2, 3, 4],
+            54321,
+        );
+    }
+
+    #[test]
+    fn part_1_3() {
+        test_sequence(
+            "3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,\
+             1002,33,7,33,1,33,31,31,1,32,31,31,4,31,99,0,0,0",
+            &[1, 0, 4, 3, 2],
+            65210,
+        );
+    }
+
+    #[test]
+    fn part_2_1() {
+        test_feedback(
+            "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,\
+             27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+            &[9, 8, 7, 6, 5],
+            139_629_729,
+        );
+    }
+
+    #[test]
+    fn part_2_2() {
+        test_feedback(
+            "3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,\
+             -5,54,1105,1,12,1,53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,\
+             53,1001,56,-1,56,1005,56,6,99,0,0,0,0,10",
+            &[9, 7, 8, 5, 6],
+            18_216,
+        );
+    }
+
+    #[test]
+    fn feedback_deadlock() {
+        let uut = AmplifierController::new("3,0,3,0,3,0,99".parse().unwrap());
+
+        let error = uut.run_feedback_loop(&[5, 6]).unwrap_err();
+        assert_eq!(
+            error,
+            AmplifierError::Deadlock {
+                amplifiers: vec![0, 1],
+                round: 1
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Amplifiers A -> B -> A are waiting on each other in round 1"
+        );
+    }
+
+    #[test]
+    fn broken_feedback_loop() {
+        // Passes on one signal, then hits an unknown opcode.
+        let uut = AmplifierController::new("3,10,3,10,4,10,3,10,42,0,0".parse().unwrap());
+
+        let error = uut.run_feedback_loop(&[0, 1]).unwrap_err();
+        assert_eq!(
+            error,
+            AmplifierError::Stopped {
+                amplifier: 0,
+                round: 2,
+                state: ProgramState::Err("Unknown opcode 42 received".to_string())
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Amplifier A failed in round 2: Unknown opcode 42 received"
+        );
+    }
+
+    #[test]
+    fn starved_sequence() {
+        let uut = AmplifierController::new("3,0,3,0,3,0,99".parse().unwrap());
+
+        assert_eq!(
+            uut.run_sequence(&[1, 2]),
+            Err(AmplifierError::Stopped {
+                amplifier: 0,
+                round: 1,
+                state: ProgramState::RequiresInput
+            })
+        );
+    }
+
+    #[test]
+    fn missing_output() {
+        let uut = AmplifierController::new("3,0,3,0,99".parse().unwrap());
+
+        assert_eq!(
+            uut.run_sequence(&[1]),
+            Err(AmplifierError::NoOutput { amplifier: 0 })
+        );
+        assert_eq!(uut.run_sequence(&[]), Err(AmplifierError::NoAmplifiers));
+    }
+
+    #[test]
+    fn amplifier_names() {
+        assert_eq!(name(0), "A");
+        assert_eq!(name(25), "Z");
+        assert_eq!(name(26), "#26");
+        assert_eq!(name(300), "#300");
+    }
+
+    #[test]
+    fn trace_sequence() {
+        let code = "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0"
+            .parse::<intcode::Program>()
+            .unwrap();
+        let (result, trace) = AmplifierController::new(code).trace_sequence(&[4, 3, 2, 1, 0]);
+
+        assert_eq!(result, Ok(43210));
+        assert_eq!(
+            trace.render(),
+            concat!(
+                "Round | A -> B   | B -> C   | C -> D   | D -> E   | E -> out\n",
+                "------+----------+----------+----------+----------+---------\n",
+                "1     | 4        | 43       | 432      | 4321     | 43210\n",
+                "2     | complete | complete | complete | complete | complete",
+            )
+        );
+    }
+
+    #[test]
+    fn trace_feedback_loop() {
+        let code = "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,\
+                    27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5"
+            .parse::<intcode::Program>()
+            .unwrap();
+        let (result, trace) = AmplifierController::new(code).trace_feedback_loop(&[9, 8, 7, 6, 5]);
+
+        assert_eq!(result, Ok(139_629_729));
+        assert_eq!(trace.rounds.len(), 6);
+        assert_eq!(trace.rounds[4][4], vec![ProgramState::Output(139_629_729)]);
+        assert!(trace.rounds[5]
+            .iter()
+            .all(|states| states == &vec![ProgramState::Complete]));
+    }
+}
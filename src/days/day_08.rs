@@ -0,0 +1,213 @@
+use crate::parse::ParseError;
+use crate::{Answer, Error, Solution};
+use std::convert::From;
+
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+enum Color {
+    Black,
+    White,
+    Transparent,
+}
+
+impl From<Color> for u8 {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Black => 0,
+            Color::White => 1,
+            Color::Transparent => 2,
+        }
+    }
+}
+
+impl From<&Color> for u8 {
+    fn from(color: &Color) -> Self {
+        match color {
+            Color::Black => 0,
+            Color::White => 1,
+            Color::Transparent => 2,
+        }
+    }
+}
+
+impl From<&Color> for char {
+    fn from(color: &Color) -> Self {
+        match color {
+            Color::Black => '0',
+            Color::White => '1',
+            Color::Transparent => '2',
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ImageLayer {
+    pixels: Vec<Color>,
+}
+
+pub struct Image {
+    width: usize,
+    height: usize,
+    layers: Vec<ImageLayer>,
+}
+
+impl Solution for Image {
+    type Err = Error;
+
+    fn parse(input: &str) -> Result<Self, Self::Err> {
+        let pixel_data = input.trim();
+        Image::new(WIDTH, HEIGHT, pixel_data)
+            .map_err(|err| Error::Parse(err.within(input, pixel_data).on_day(8)))
+    }
+
+    fn part_1(&self) -> Result<Answer, Self::Err> {
+        let layer = self
+            .layers
+            .iter()
+            .min_by_key(|layer| layer.count_color(Color::Black))
+            .ok_or_else(|| Error::NoAnswer("Image has no layers".to_string()))?;
+
+        Ok((layer.count_color(Color::White) * layer.count_color(Color::Transparent)).into())
+    }
+
+    fn part_2(&self) -> Result<Answer, Self::Err> {
+        let rows = self
+            .decode()
+            .message()
+            .chunks(self.width)
+            .map(|row| row.iter().map(|&pixel| pixel != 0).collect())
+            .collect();
+
+        Ok(Answer::Image(rows))
+    }
+}
+
+impl ImageLayer {
+    fn message(&self) -> Vec<u8> {
+        self.pixels.iter().map(u8::from).collect()
+    }
+
+    fn count_color(&self, color: Color) -> usize {
+        self.pixels.iter().filter(|c| **c == color).count()
+    }
+
+    fn new(width: usize, height: usize) -> ImageLayer {
+        ImageLayer {
+            pixels: vec![Color::Transparent; width * height],
+        }
+    }
+
+    fn stack(&mut self, other: &ImageLayer) {
+        assert!(self.pixels.len() == other.pixels.len());
+
+        for i in 0..self.pixels.len() {
+            if other.pixels[i] != Color::Transparent {
+                self.pixels[i] = other.pixels[i].clone();
+            }
+        }
+    }
+}
+
+impl Image {
+    /// Stacks the layers, the first on top, letting transparent pixels show
+    /// the layers below.
+    fn decode(&self) -> ImageLayer {
+        let mut decoded = ImageLayer::new(self.width, self.height);
+
+        self.layers
+            .iter()
+            .rev()
+            .for_each(|layer| decoded.stack(layer));
+
+        decoded
+    }
+
+    /// Splits `pixel_data`, one digit per pixel, into layers of `width` by
+    /// `height` pixels.
+    pub fn new(width: usize, height: usize, pixel_data: &str) -> Result<Image, ParseError> {
+        if width == 0 || height == 0 {
+            return Err(ParseError::at(
+                pixel_data,
+                &pixel_data[..0],
+                format!(
+                    "expected layers of at least one pixel, found {} by {}",
+                    width, height
+                ),
+            ));
+        }
+
+        let pixels = pixel_data
+            .char_indices()
+            .map(|(i, c)| match c {
+                '0' => Ok(Color::Black),
+                '1' => Ok(Color::White),
+                '2' => Ok(Color::Transparent),
+                _ => Err(ParseError::at(
+                    pixel_data,
+                    &pixel_data[i..i + c.len_utf8()],
+                    "expected a color 0, 1 or 2",
+                )),
+            })
+            .collect::<Result<Vec<Color>, ParseError>>()?;
+
+        let n_pixels = width * height;
+        let n_layers = pixels.len() / n_pixels;
+
+        // Every pixel is a single byte by now, so counts of pixels are also
+        // offsets into the text.
+        if n_pixels * n_layers != pixels.len() {
+            return Err(ParseError::at(
+                pixel_data,
+                &pixel_data[n_pixels * n_layers..],
+                format!(
+                    "expected whole layers of {} by {} pixels, found {} pixels",
+                    width,
+                    height,
+                    pixels.len()
+                ),
+            ));
+        }
+
+        let mut layers = vec![ImageLayer::new(width, height); n_layers];
+        for i in 0..n_layers {
+            layers[i]
+                .pixels
+                .clone_from_slice(&pixels[(i * n_pixels)..(i * n_pixels + n_pixels)]);
+        }
+
+        Ok(Image {
+            width,
+            height,
+            layers,
+        })
+    }
+}
+
+#[cfg(test)]
+mod day_08 {
+    use super::*;
+
+    #[test]
+    fn layers() {
+        let image = Image::new(3, 2, "001122111000").unwrap();
+
+        assert_eq!(image.layers.len(), 2);
+        assert_eq!(image.layers[0].count_color(Color::White), 2);
+        assert_eq!(image.layers[1].count_color(Color::Black), 3);
+    }
+
+    #[test]
+    fn decode() {
+        let image = Image::new(2, 2, "0222112222120000").unwrap();
+
+        assert_eq!(image.decode().message(), vec![0, 1, 1, 0]);
+    }
+
+    #[test]
+    fn empty_layers() {
+        assert!(Image::new(0, 2, "0011").is_err());
+        assert!(Image::new(3, 0, "").is_err());
+    }
+}
@@ -0,0 +1,121 @@
+// Each day's tests live in a module named after the day.
+#![allow(clippy::module_inception)]
+
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
+pub mod day_05;
+pub mod day_06;
+pub mod day_07;
+pub mod day_08;
+
+use crate::bench::{self, Budget, Measurement};
+use crate::{solve_part_1, solve_part_2, Answer};
+
+/// Solves one part of a puzzle given the puzzle input.
+pub type Solver = fn(&str) -> Result<Answer, String>;
+
+/// Explains how a puzzle was solved given the puzzle input.
+pub type Tracer = fn(&str) -> Result<String, String>;
+
+/// Times parsing and solving both parts given the puzzle input.
+pub type Bencher = fn(&str, &Budget) -> Result<Vec<Measurement>, String>;
+
+pub struct Day {
+    pub number: u8,
+    pub part_1: Solver,
+    pub part_2: Solver,
+    /// Explains how the answers were found, for days that support it.
+    pub trace: Option<Tracer>,
+    pub bench: Bencher,
+    /// The shipped input, compiled in by the `embed-inputs` feature.
+    pub input: Option<&'static str>,
+}
+
+/// The shipped input `src/inputs/$file`, when the `embed-inputs` feature
+/// compiles it into the binary.
+#[cfg(feature = "embed-inputs")]
+macro_rules! embedded {
+    ($file:literal) => {
+        Some(include_str!(concat!("../inputs/", $file)))
+    };
+}
+
+#[cfg(not(feature = "embed-inputs"))]
+macro_rules! embedded {
+    ($file:literal) => {
+        None
+    };
+}
+
+pub const DAYS: [Day; 8] = [
+    Day {
+        number: 1,
+        part_1: solve_part_1::<day_01::Modules>,
+        part_2: solve_part_2::<day_01::Modules>,
+        trace: None,
+        bench: bench::solution::<day_01::Modules>,
+        input: embedded!("day_01"),
+    },
+    Day {
+        number: 2,
+        part_1: solve_part_1::<day_02::GravityAssist>,
+        part_2: solve_part_2::<day_02::GravityAssist>,
+        trace: None,
+        bench: bench::solution::<day_02::GravityAssist>,
+        input: embedded!("day_02"),
+    },
+    Day {
+        number: 3,
+        part_1: solve_part_1::<day_03::Circuit>,
+        part_2: solve_part_2::<day_03::Circuit>,
+        trace: None,
+        bench: bench::solution::<day_03::Circuit>,
+        input: embedded!("day_03"),
+    },
+    Day {
+        number: 4,
+        part_1: solve_part_1::<day_04::Passwords>,
+        part_2: solve_part_2::<day_04::Passwords>,
+        trace: None,
+        bench: bench::solution::<day_04::Passwords>,
+        input: embedded!("day_04"),
+    },
+    Day {
+        number: 5,
+        part_1: solve_part_1::<day_05::Diagnostic>,
+        part_2: solve_part_2::<day_05::Diagnostic>,
+        trace: None,
+        bench: bench::solution::<day_05::Diagnostic>,
+        input: embedded!("day_05"),
+    },
+    Day {
+        number: 6,
+        part_1: solve_part_1::<day_06::System>,
+        part_2: solve_part_2::<day_06::System>,
+        trace: None,
+        bench: bench::solution::<day_06::System>,
+        input: embedded!("day_06"),
+    },
+    Day {
+        number: 7,
+        part_1: solve_part_1::<day_07::AmplifierController>,
+        part_2: solve_part_2::<day_07::AmplifierController>,
+        trace: Some(day_07::trace),
+        bench: bench::solution::<day_07::AmplifierController>,
+        input: embedded!("day_07"),
+    },
+    Day {
+        number: 8,
+        part_1: solve_part_1::<day_08::Image>,
+        part_2: solve_part_2::<day_08::Image>,
+        trace: None,
+        bench: bench::solution::<day_08::Image>,
+        input: embedded!("day_08"),
+    },
+];
+
+pub fn get(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
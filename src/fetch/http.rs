@@ -0,0 +1,216 @@
+//! A minimal HTTP GET. Plain `http://` URLs are requested directly, and
+//! `https://` ones through `curl`, which keeps TLS out of the dependencies.
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// The value of the first header called `name`, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Requests `url` with the extra `headers`, without following redirects.
+pub fn get(url: &str, headers: &[(&str, &str)]) -> Result<Response, String> {
+    if let Some(rest) = url.strip_prefix("http://") {
+        get_direct(rest, headers)
+    } else if url.starts_with("https://") {
+        get_curl(url, headers)
+    } else {
+        Err(format!("Unsupported URL '{}', expected http or https", url))
+    }
+}
+
+fn get_direct(url: &str, headers: &[(&str, &str)]) -> Result<Response, String> {
+    let (authority, path) = match url.find('/') {
+        Some(split) => url.split_at(split),
+        None => (url, "/"),
+    };
+    let address = if authority.contains(':') {
+        authority.to_string()
+    } else {
+        format!("{}:80", authority)
+    };
+
+    let fail = |err: std::io::Error| format!("Request to {} failed: {}", authority, err);
+    let mut stream = TcpStream::connect(&address).map_err(fail)?;
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(fail)?;
+    stream.set_write_timeout(Some(TIMEOUT)).map_err(fail)?;
+
+    let mut request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+        path, authority
+    );
+    for (name, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).map_err(fail)?;
+
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw).map_err(fail)?;
+
+    parse(&raw, true)
+}
+
+fn get_curl(url: &str, headers: &[(&str, &str)]) -> Result<Response, String> {
+    // Headers go through stdin so that cookies stay out of the process list.
+    let mut curl = Command::new("curl")
+        .args(["--silent", "--show-error", "--dump-header", "-"])
+        .args(["--max-time", &TIMEOUT.as_secs().to_string()])
+        .args(["--header", "@-", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Failed to run curl for {}: {}", url, err))?;
+
+    let headers = headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}\n", name, value))
+        .collect::<String>();
+    curl.stdin
+        .take()
+        .expect("curl's stdin is piped")
+        .write_all(headers.as_bytes())
+        .map_err(|err| format!("Failed to pass headers to curl: {}", err))?;
+
+    let output = curl
+        .wait_with_output()
+        .map_err(|err| format!("curl failed for {}: {}", url, err))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Request to {} failed: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    // curl has already decoded the body, whatever the headers say.
+    parse(&output.stdout, false)
+}
+
+/// Splits a raw response into its status, headers and body, decoding a
+/// chunked body if `decode` is set.
+fn parse(raw: &[u8], decode: bool) -> Result<Response, String> {
+    let end = raw
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or("Response has no end of headers")?;
+    let head = String::from_utf8_lossy(&raw[..end]);
+    let mut body = raw[end + 4..].to_vec();
+
+    let mut lines = head.split("\r\n");
+    let status = lines
+        .next()
+        .and_then(|line| line.split(' ').nth(1))
+        .and_then(|status| status.parse().ok())
+        .ok_or("Response has no status")?;
+    let headers = lines
+        .filter_map(|line| {
+            let split = line.find(':')?;
+            Some((
+                line[..split].trim().to_string(),
+                line[split + 1..].trim().to_string(),
+            ))
+        })
+        .collect::<Vec<(String, String)>>();
+
+    let mut response = Response {
+        status,
+        headers,
+        body: Vec::new(),
+    };
+    if decode
+        && response
+            .header("Transfer-Encoding")
+            .is_some_and(|encoding| encoding.eq_ignore_ascii_case("chunked"))
+    {
+        body = dechunk(&body)?;
+    } else if let Some(length) = response.header("Content-Length") {
+        let length = length.parse().map_err(|_| "Invalid Content-Length")?;
+        body.truncate(length);
+    }
+    response.body = body;
+
+    Ok(response)
+}
+
+fn dechunk(mut chunked: &[u8]) -> Result<Vec<u8>, String> {
+    let mut body = Vec::new();
+    loop {
+        let line = chunked
+            .windows(2)
+            .position(|window| window == b"\r\n")
+            .ok_or("Chunk has no size")?;
+        let size = String::from_utf8_lossy(&chunked[..line]);
+        let size = usize::from_str_radix(size.split(';').next().unwrap_or("").trim(), 16)
+            .map_err(|_| format!("Invalid chunk size '{}'", size))?;
+        if size == 0 {
+            return Ok(body);
+        }
+
+        let chunk = chunked
+            .get(line + 2..line + 2 + size)
+            .ok_or("Chunk is truncated")?;
+        body.extend_from_slice(chunk);
+        chunked = chunked.get(line + 4 + size..).unwrap_or(&[]);
+    }
+}
+
+#[cfg(test)]
+mod responses {
+    use super::*;
+
+    #[test]
+    fn content_length() {
+        let response = parse(
+            b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\nretry-after: 3\r\n\r\nhello, and more",
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.header("Retry-After"), Some("3"));
+        assert_eq!(response.body, b"hello");
+    }
+
+    #[test]
+    fn chunked() {
+        let raw = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+                    5\r\nhello\r\n7;ext=1\r\n, world\r\n0\r\n\r\n";
+
+        assert_eq!(parse(raw, true).unwrap().body, b"hello, world");
+        assert!(parse(raw, false)
+            .unwrap()
+            .body
+            .starts_with(b"5\r\nhello\r\n"));
+        assert!(parse(
+            b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\nz\r\n",
+            true
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse(b"HTTP/1.1 200 OK\r\n", true).is_err());
+        assert!(parse(b"garbage\r\n\r\n", true).is_err());
+        assert!(get("ftp://example.com/", &[]).is_err());
+    }
+}
@@ -0,0 +1,432 @@
+//! Downloads puzzle inputs into the input cache.
+
+mod http;
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2019";
+
+/// Holds the value of the `session` cookie of a logged in browser.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Overrides `DEFAULT_BASE_URL`, for mirrors and local test servers.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (aoc fetch)"
+);
+
+/// Fetches inputs from `{base_url}/day/{day}/input` into `cache_dir`,
+/// unless they are already there.
+#[derive(Debug, Clone)]
+pub struct Fetcher {
+    pub base_url: String,
+    pub session: Option<String>,
+    pub cache_dir: PathBuf,
+    /// Only use inputs that are already cached.
+    pub offline: bool,
+    /// Download inputs even if they are cached.
+    pub refresh: bool,
+    /// How often to retry when rate limited or the server is unavailable.
+    pub retries: u32,
+    /// How long to wait before the first retry, doubling for each one after
+    /// up to 1024 times as long, unless the server asks for a specific wait
+    /// with `Retry-After`, which is held to the same limit.
+    pub backoff: Duration,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// The input is not cached and downloads are disabled.
+    Offline {
+        day: u8,
+        path: PathBuf,
+    },
+    MissingSession,
+    /// The puzzle does not exist or is not unlocked yet.
+    NotAvailable {
+        day: u8,
+    },
+    /// The server kept asking to slow down, or was unavailable.
+    RateLimited {
+        day: u8,
+        attempts: u32,
+    },
+    /// The server answered with an unexpected status, which for an invalid
+    /// session is 400 or a redirect to the login page.
+    Status {
+        day: u8,
+        status: u16,
+        message: String,
+    },
+    Request {
+        day: u8,
+        error: String,
+    },
+    Cache {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::Offline { day, path } => write!(
+                f,
+                "Day {} is not cached at {} and downloads are disabled",
+                day,
+                path.display()
+            ),
+            FetchError::MissingSession => write!(
+                f,
+                "No session cookie, pass --session or set {}",
+                SESSION_VAR
+            ),
+            FetchError::NotAvailable { day } => {
+                write!(f, "Day {} is not available yet", day)
+            }
+            FetchError::RateLimited { day, attempts } => write!(
+                f,
+                "Day {} is still rate limited after {} attempts",
+                day, attempts
+            ),
+            FetchError::Status {
+                day,
+                status,
+                message,
+            } => write!(f, "Day {} failed with status {}: {}", day, status, message),
+            FetchError::Request { day, error } => write!(f, "Day {}: {}", day, error),
+            FetchError::Cache { path, error } => {
+                write!(f, "Failed to cache {}: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl Fetcher {
+    pub fn new(cache_dir: PathBuf) -> Fetcher {
+        Fetcher {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: None,
+            cache_dir,
+            offline: false,
+            refresh: false,
+            retries: 3,
+            backoff: Duration::from_secs(1),
+        }
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.cache_dir.join(format!("day_{:02}", day))
+    }
+
+    pub fn fetch(&self, day: u8) -> Result<Fetched, FetchError> {
+        let path = self.path(day);
+        let cached = std::fs::metadata(&path).is_ok_and(|file| file.len() > 0);
+
+        if cached && !self.refresh {
+            return Ok(Fetched::Cached(path));
+        }
+        if self.offline {
+            return if cached {
+                Ok(Fetched::Cached(path))
+            } else {
+                Err(FetchError::Offline { day, path })
+            };
+        }
+
+        let input = self.download(day)?;
+
+        let cache = |error| FetchError::Cache {
+            path: path.clone(),
+            error,
+        };
+        // Written next to the cache and renamed, so that an interrupted
+        // download never leaves a partial input behind.
+        let partial = path.with_extension("partial");
+        std::fs::create_dir_all(&self.cache_dir).map_err(cache)?;
+        std::fs::write(&partial, input).map_err(cache)?;
+        std::fs::rename(&partial, &path).map_err(cache)?;
+
+        Ok(Fetched::Downloaded(path))
+    }
+
+    fn download(&self, day: u8) -> Result<Vec<u8>, FetchError> {
+        let session = self.session.as_ref().ok_or(FetchError::MissingSession)?;
+        let url = format!("{}/day/{}/input", self.base_url.trim_end_matches('/'), day);
+        let cookie = format!("session={}", session);
+        let headers = [("Cookie", cookie.as_str()), ("User-Agent", USER_AGENT)];
+
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let retry = attempt <= self.retries;
+
+            let response = match http::get(&url, &headers) {
+                Ok(response) => response,
+                Err(_) if retry => {
+                    std::thread::sleep(self.wait(attempt, None));
+                    continue;
+                }
+                Err(error) => return Err(FetchError::Request { day, error }),
+            };
+
+            match response.status {
+                200 => return Ok(response.body),
+                404 => return Err(FetchError::NotAvailable { day }),
+                429 | 502 | 503 | 504 if retry => {
+                    std::thread::sleep(self.wait(attempt, response.header("Retry-After")));
+                }
+                429 | 502 | 503 | 504 => {
+                    return Err(FetchError::RateLimited {
+                        day,
+                        attempts: attempt,
+                    })
+                }
+                status => {
+                    return Err(FetchError::Status {
+                        day,
+                        status,
+                        message: String::from_utf8_lossy(&response.body).trim().to_string(),
+                    })
+                }
+            }
+        }
+    }
+
+    /// How long to wait after the failed `attempt`, counting from 1, given
+    /// the server's `Retry-After` in seconds if it sent one.
+    fn wait(&self, attempt: u32, retry_after: Option<&str>) -> Duration {
+        let limit = self.backoff * 1024;
+        let backoff = self.backoff * 2_u32.pow((attempt - 1).min(10));
+
+        retry_after
+            .and_then(|seconds| seconds.trim().parse().ok())
+            .map_or(backoff, |seconds| Duration::from_secs(seconds).min(limit))
+    }
+}
+
+#[cfg(test)]
+mod fetching {
+    use super::*;
+    use crate::testing::TempDir;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    /// A stand-in for the puzzle server that serves the fixture files in
+    /// `dir`, at `/day/N/input` as `day_NN`, to requests with the session
+    /// cookie `secret`. The first `throttle` requests are rate limited.
+    struct Server {
+        url: String,
+        requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Server {
+        fn start(dir: PathBuf, throttle: usize) -> Server {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+
+            let log = Arc::clone(&requests);
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let mut stream = stream.unwrap();
+                    let mut head = Vec::new();
+                    for line in BufReader::new(&stream).lines() {
+                        let line = line.unwrap();
+                        if line.is_empty() {
+                            break;
+                        }
+                        head.push(line);
+                    }
+
+                    let path = head[0].split(' ').nth(1).unwrap().to_string();
+                    let authorized = head.iter().any(|line| line == "Cookie: session=secret");
+                    let day = path
+                        .strip_prefix("/day/")
+                        .and_then(|path| path.strip_suffix("/input"))
+                        .and_then(|day| day.parse::<u8>().ok());
+                    let throttled = log.lock().unwrap().len() < throttle;
+                    log.lock().unwrap().push(path);
+
+                    let (status, headers, body) = match day {
+                        _ if throttled => {
+                            ("429 Too Many Requests", "Retry-After: 0\r\n", String::new())
+                        }
+                        _ if !authorized => (
+                            "400 Bad Request",
+                            "",
+                            "Puzzle inputs differ by user.".to_string(),
+                        ),
+                        Some(day) => {
+                            match std::fs::read_to_string(dir.join(format!("day_{:02}", day))) {
+                                Ok(input) => ("200 OK", "", input),
+                                Err(_) => ("404 Not Found", "", "Not found".to_string()),
+                            }
+                        }
+                        None => ("404 Not Found", "", "Not found".to_string()),
+                    };
+                    let _ = write!(
+                        stream,
+                        "HTTP/1.1 {}\r\n{}Content-Length: {}\r\n\r\n{}",
+                        status,
+                        headers,
+                        body.len(),
+                        body
+                    );
+                }
+            });
+
+            Server { url, requests }
+        }
+
+        fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn fetcher(server: &Server, cache: PathBuf) -> Fetcher {
+        Fetcher {
+            base_url: format!("{}/", server.url),
+            session: Some("secret".to_string()),
+            backoff: Duration::from_millis(1),
+            ..Fetcher::new(cache)
+        }
+    }
+
+    #[test]
+    fn downloads_and_caches() {
+        let fixtures = TempDir::new("fetch-fixtures", &[("day_01", "12\n14\n")]);
+        let server = Server::start(fixtures.to_path_buf(), 0);
+        let cache = TempDir::new("fetch-cache", &[]);
+        let fetcher = fetcher(&server, cache.to_path_buf());
+
+        assert_eq!(
+            fetcher.fetch(1).unwrap(),
+            Fetched::Downloaded(cache.join("day_01"))
+        );
+        assert_eq!(
+            std::fs::read_to_string(cache.join("day_01")).unwrap(),
+            "12\n14\n"
+        );
+        assert_eq!(
+            fetcher.fetch(1).unwrap(),
+            Fetched::Cached(cache.join("day_01"))
+        );
+        assert_eq!(server.requests(), vec!["/day/1/input"]);
+
+        let refresh = Fetcher {
+            refresh: true,
+            ..fetcher
+        };
+        assert_eq!(
+            refresh.fetch(1).unwrap(),
+            Fetched::Downloaded(cache.join("day_01"))
+        );
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn backs_off_when_rate_limited() {
+        let fixtures = TempDir::new("fetch-throttled", &[("day_02", "1,0,0,3,99")]);
+        let cache = TempDir::new("fetch-throttled-cache", &[]);
+
+        let server = Server::start(fixtures.to_path_buf(), 2);
+        let patient = fetcher(&server, cache.to_path_buf());
+        assert!(matches!(patient.fetch(2), Ok(Fetched::Downloaded(_))));
+        assert_eq!(server.requests().len(), 3);
+
+        let server = Server::start(fixtures.to_path_buf(), 10);
+        let impatient = Fetcher {
+            retries: 2,
+            refresh: true,
+            ..fetcher(&server, cache.to_path_buf())
+        };
+        assert!(matches!(
+            impatient.fetch(2),
+            Err(FetchError::RateLimited {
+                day: 2,
+                attempts: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn waits() {
+        let fetcher = Fetcher::new(PathBuf::new());
+
+        assert_eq!(fetcher.wait(1, None), Duration::from_secs(1));
+        assert_eq!(fetcher.wait(3, None), Duration::from_secs(4));
+        assert_eq!(fetcher.wait(30, None), Duration::from_secs(1024));
+        assert_eq!(fetcher.wait(1, Some("0")), Duration::from_secs(0));
+        assert_eq!(fetcher.wait(1, Some("60")), Duration::from_secs(60));
+        assert_eq!(fetcher.wait(1, Some("soon")), Duration::from_secs(1));
+        assert_eq!(
+            fetcher.wait(1, Some("999999999")),
+            Duration::from_secs(1024)
+        );
+    }
+
+    #[test]
+    fn offline() {
+        let cache = TempDir::new("fetch-offline", &[("day_03", "R8,U5\nU7,R6\n")]);
+        let fetcher = Fetcher {
+            offline: true,
+            refresh: true,
+            ..Fetcher::new(cache.to_path_buf())
+        };
+
+        assert_eq!(
+            fetcher.fetch(3).unwrap(),
+            Fetched::Cached(cache.join("day_03"))
+        );
+        assert_eq!(
+            fetcher.fetch(4).unwrap_err().to_string(),
+            format!(
+                "Day 4 is not cached at {} and downloads are disabled",
+                cache.join("day_04").display()
+            )
+        );
+    }
+
+    #[test]
+    fn failures() {
+        let fixtures = TempDir::new("fetch-failures", &[]);
+        let server = Server::start(fixtures.to_path_buf(), 0);
+        let cache = TempDir::new("fetch-failures-cache", &[]);
+
+        assert!(matches!(
+            fetcher(&server, cache.to_path_buf()).fetch(25),
+            Err(FetchError::NotAvailable { day: 25 })
+        ));
+        let error = Fetcher {
+            session: Some("wrong".to_string()),
+            ..fetcher(&server, cache.to_path_buf())
+        }
+        .fetch(1)
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Day 1 failed with status 400: Puzzle inputs differ by user."
+        );
+        assert!(matches!(
+            Fetcher::new(cache.to_path_buf()).fetch(1),
+            Err(FetchError::MissingSession)
+        ));
+        assert!(!cache.join("day_01").exists());
+    }
+}
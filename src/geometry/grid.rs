@@ -0,0 +1,290 @@
+use super::{Direction, Point, Vector};
+use crate::parse::ParseError;
+use std::ops::{Index, IndexMut};
+
+/// A rectangular grid of cells, with `Point::ORIGIN` at the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// The smallest rectangle containing a set of points, inclusive of both
+/// corners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses one row per line, with `cell` converting each character or
+    /// giving the reason it cannot. Every row must be as wide as the first.
+    pub fn parse<F>(text: &str, mut cell: F) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(char) -> Result<T, String>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for line in text.lines() {
+            let row = line
+                .char_indices()
+                .map(|(i, c)| {
+                    cell(c).map_err(|err| ParseError::at(text, &line[i..i + c.len_utf8()], err))
+                })
+                .collect::<Result<Vec<T>, ParseError>>()?;
+
+            match width {
+                None => width = Some(row.len()),
+                Some(width) if width != row.len() => {
+                    return Err(ParseError::at(
+                        text,
+                        line,
+                        format!("expected {} cells but found {}", width, row.len()),
+                    ))
+                }
+                Some(_) => {}
+            }
+
+            cells.extend(row);
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        Bounds {
+            min: Point::ORIGIN,
+            max: Point::new(self.width as i64 - 1, self.height as i64 - 1),
+        }
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.offset(point).is_some()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(move |index| &mut self.cells[index])
+    }
+
+    /// Every point in reading order, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width as i64;
+        (0..self.cells.len() as i64).map(move |index| Point::new(index % width, index / width))
+    }
+
+    /// Every cell with its point, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The first point, in reading order, whose cell matches `predicate`.
+    pub fn find<P>(&self, mut predicate: P) -> Option<Point>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// The points above, below and either side of `point` within the grid.
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .iter()
+            .map(move |direction| point.step(*direction, 1))
+            .filter(move |neighbour| self.contains(*neighbour))
+    }
+
+    /// Like `neighbours`, including the diagonals.
+    pub fn neighbours_diagonal(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        (-1..=1)
+            .flat_map(|y| (-1..=1).map(move |x| Vector::new(x, y)))
+            .filter(|vector| *vector != Vector::new(0, 0))
+            .map(move |vector| point + vector)
+            .filter(move |neighbour| self.contains(*neighbour))
+    }
+
+    /// Draws one line per row, with `cell` choosing each character.
+    pub fn render<F>(&self, mut cell: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        self.cells
+            .chunks(self.width.max(1))
+            .map(|row| row.iter().map(&mut cell).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        if (0..self.width as i64).contains(&point.x) && (0..self.height as i64).contains(&point.y) {
+            Some(point.y as usize * self.width + point.x as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+impl std::str::FromStr for Grid<char> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, Ok)
+    }
+}
+
+impl std::fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|cell| *cell))
+    }
+}
+
+impl Bounds {
+    /// The bounds of `points`, or `None` if there are none.
+    pub fn of<I>(points: I) -> Option<Bounds>
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        points.into_iter().fold(None, |bounds, point| {
+            Some(match bounds {
+                None => Bounds {
+                    min: point,
+                    max: point,
+                },
+                Some(Bounds { min, max }) => Bounds {
+                    min: Point::new(min.x.min(point.x), min.y.min(point.y)),
+                    max: Point::new(max.x.max(point.x), max.y.max(point.y)),
+                },
+            })
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+}
+
+#[cfg(test)]
+mod grids {
+    use super::*;
+
+    const MAZE: &str = "#####\n#..E#\n#S###";
+
+    #[test]
+    fn parses_and_renders() {
+        let grid = MAZE.parse::<Grid<char>>().unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (5, 3));
+        assert_eq!(grid[Point::new(3, 1)], 'E');
+        assert_eq!(grid.find(|cell| *cell == 'S'), Some(Point::new(1, 2)));
+        assert_eq!(grid.get(Point::new(5, 0)), None);
+        assert_eq!(grid.to_string(), MAZE);
+
+        let walls = Grid::parse(MAZE, |c| Ok(c == '#')).unwrap();
+        assert_eq!(walls.iter().filter(|(_, wall)| **wall).count(), 11);
+        assert_eq!(
+            walls.render(|wall| if *wall { '#' } else { '.' }),
+            "#####\n#...#\n#.###"
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            "###\n##".parse::<Grid<char>>().unwrap_err().to_string(),
+            "2:1: expected 3 cells but found 2 at \"##\""
+        );
+        assert_eq!(
+            Grid::parse("##\n#x", |c| if c == '#' {
+                Ok(true)
+            } else {
+                Err("expected a wall".to_string())
+            })
+            .unwrap_err()
+            .to_string(),
+            "2:2: expected a wall at \"x\""
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        let mut grid = Grid::new(3, 2, 0);
+        grid[Point::new(2, 1)] = 7;
+
+        assert_eq!(
+            grid.neighbours(Point::new(0, 0)).collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours(Point::new(1, 1)).count(), 3);
+        assert_eq!(grid.neighbours_diagonal(Point::new(1, 0)).count(), 5);
+        assert_eq!(grid.find(|cell| *cell == 7), Some(Point::new(2, 1)));
+    }
+
+    #[test]
+    fn bounds() {
+        let bounds = Bounds::of(vec![Point::new(-2, 3), Point::new(4, -1)]).unwrap();
+
+        assert_eq!(bounds.min, Point::new(-2, -1));
+        assert_eq!((bounds.width(), bounds.height()), (7, 5));
+        assert!(bounds.contains(Point::ORIGIN));
+        assert!(!bounds.contains(Point::new(5, 0)));
+        assert_eq!(Bounds::of(Vec::new()), None);
+        assert_eq!(Grid::new(3, 2, ' ').bounds().max, Point::new(2, 1));
+    }
+}
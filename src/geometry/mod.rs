@@ -0,0 +1,377 @@
+//! Integer points and vectors in two and three dimensions, the four grid
+//! directions and the usual metrics between points.
+//!
+//! The y axis grows downwards, as in text, so that `Direction::Up` moves a
+//! point to the row above it in a `Grid`.
+
+mod grid;
+
+pub use grid::{Bounds, Grid};
+
+use crate::parse::ParseError;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// The displacement between two `Point`s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vector3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    /// The point `distance` steps away in `direction`.
+    pub fn step(self, direction: Direction, distance: i64) -> Point {
+        self + direction.vector() * distance
+    }
+
+    pub fn manhattan(self, other: Point) -> u64 {
+        (other - self).manhattan()
+    }
+
+    pub fn chebyshev(self, other: Point) -> u64 {
+        (other - self).chebyshev()
+    }
+
+    pub fn euclidean(self, other: Point) -> f64 {
+        (other - self).euclidean()
+    }
+}
+
+impl Vector {
+    pub fn new(x: i64, y: i64) -> Vector {
+        Vector { x, y }
+    }
+
+    pub fn manhattan(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    pub fn chebyshev(self) -> u64 {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+
+    pub fn euclidean(self) -> f64 {
+        ((self.x * self.x + self.y * self.y) as f64).sqrt()
+    }
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub fn new(x: i64, y: i64, z: i64) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Point3) -> u64 {
+        (other - self).manhattan()
+    }
+
+    pub fn chebyshev(self, other: Point3) -> u64 {
+        (other - self).chebyshev()
+    }
+
+    pub fn euclidean(self, other: Point3) -> f64 {
+        (other - self).euclidean()
+    }
+}
+
+impl Vector3 {
+    pub fn new(x: i64, y: i64, z: i64) -> Vector3 {
+        Vector3 { x, y, z }
+    }
+
+    pub fn manhattan(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs() + self.z.unsigned_abs()
+    }
+
+    pub fn chebyshev(self) -> u64 {
+        self.x
+            .unsigned_abs()
+            .max(self.y.unsigned_abs())
+            .max(self.z.unsigned_abs())
+    }
+
+    pub fn euclidean(self) -> f64 {
+        ((self.x * self.x + self.y * self.y + self.z * self.z) as f64).sqrt()
+    }
+}
+
+impl Direction {
+    /// In clockwise order, starting from `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn reverse(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    pub fn is_vertical(self) -> bool {
+        self == Direction::Up || self == Direction::Down
+    }
+
+    /// A single step in this direction.
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction::Up => Vector::new(0, -1),
+            Direction::Down => Vector::new(0, 1),
+            Direction::Left => Vector::new(-1, 0),
+            Direction::Right => Vector::new(1, 0),
+        }
+    }
+}
+
+impl std::str::FromStr for Direction {
+    type Err = ParseError;
+
+    /// Parses `U`, `D`, `L` and `R`, or the directions' full names.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "U" | "Up" => Ok(Direction::Up),
+            "D" | "Down" => Ok(Direction::Down),
+            "L" | "Left" => Ok(Direction::Left),
+            "R" | "Right" => Ok(Direction::Right),
+            _ => Err(ParseError::at(s, s, "expected a direction U, D, L or R")),
+        }
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, vector: Vector) -> Point {
+        Point::new(self.x + vector.x, self.y + vector.y)
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, vector: Vector) -> Point {
+        Point::new(self.x - vector.x, self.y - vector.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, vector: Vector) {
+        *self = *self + vector;
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, vector: Vector) {
+        *self = *self - vector;
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, factor: i64) -> Vector {
+        Vector::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl Add<Vector3> for Point3 {
+    type Output = Point3;
+
+    fn add(self, vector: Vector3) -> Point3 {
+        Point3::new(self.x + vector.x, self.y + vector.y, self.z + vector.z)
+    }
+}
+
+impl Sub<Vector3> for Point3 {
+    type Output = Point3;
+
+    fn sub(self, vector: Vector3) -> Point3 {
+        Point3::new(self.x - vector.x, self.y - vector.y, self.z - vector.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Vector3;
+
+    fn sub(self, other: Point3) -> Vector3 {
+        Vector3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl AddAssign<Vector3> for Point3 {
+    fn add_assign(&mut self, vector: Vector3) {
+        *self = *self + vector;
+    }
+}
+
+impl SubAssign<Vector3> for Point3 {
+    fn sub_assign(&mut self, vector: Vector3) {
+        *self = *self - vector;
+    }
+}
+
+impl Add for Vector3 {
+    type Output = Vector3;
+
+    fn add(self, other: Vector3) -> Vector3 {
+        Vector3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Vector3 {
+    type Output = Vector3;
+
+    fn sub(self, other: Vector3) -> Vector3 {
+        Vector3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Mul<i64> for Vector3 {
+    type Output = Vector3;
+
+    fn mul(self, factor: i64) -> Vector3 {
+        Vector3::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl Neg for Vector3 {
+    type Output = Vector3;
+
+    fn neg(self) -> Vector3 {
+        Vector3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+#[cfg(test)]
+mod points {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let point = Point::new(3, -4);
+
+        assert_eq!(point + Vector::new(1, 1), Point::new(4, -3));
+        assert_eq!(point - Point::ORIGIN, Vector::new(3, -4));
+        assert_eq!(-(Vector::new(1, 2) * 3), Vector::new(-3, -6));
+        assert_eq!(point.step(Direction::Up, 2), Point::new(3, -6));
+        assert_eq!(
+            Point3::new(1, 2, 3) - Point3::new(3, 2, 1),
+            Vector3::new(-2, 0, 2)
+        );
+    }
+
+    #[test]
+    fn metrics() {
+        let point = Point::new(3, -4);
+
+        assert_eq!(Point::ORIGIN.manhattan(point), 7);
+        assert_eq!(Point::ORIGIN.chebyshev(point), 4);
+        assert_eq!(Point::ORIGIN.euclidean(point), 5.0);
+        assert_eq!(Point3::ORIGIN.manhattan(Point3::new(-1, 2, -3)), 6);
+        assert_eq!(Point3::ORIGIN.chebyshev(Point3::new(-1, 2, -3)), 3);
+        assert_eq!(Point3::ORIGIN.euclidean(Point3::new(2, 3, 6)), 7.0);
+    }
+
+    #[test]
+    fn turns() {
+        for direction in Direction::ALL.iter().copied() {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+            assert_eq!(direction.reverse().vector(), -direction.vector());
+        }
+
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!("L".parse::<Direction>(), Ok(Direction::Left));
+        assert!("X".parse::<Direction>().is_err());
+    }
+}
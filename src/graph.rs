@@ -0,0 +1,505 @@
+//! Graph searches, both over an explicit `Graph` and over implicit graphs
+//! given as a function from a node to its neighbours, such as the open
+//! cells of a maze.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Nodes of any hashable ID joined by weighted edges, either all directed or
+/// all undirected. Nodes are kept in the order they were added, which is the
+/// order every search visits them in.
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    directed: bool,
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    outgoing: Vec<Vec<(usize, u64)>>,
+    incoming: Vec<Vec<(usize, u64)>>,
+}
+
+/// The nodes of a shortest path, from start to goal inclusive, and its
+/// total weight.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub cost: u64,
+    pub nodes: Vec<N>,
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn directed() -> Graph<N> {
+        Graph::new(true)
+    }
+
+    pub fn undirected() -> Graph<N> {
+        Graph::new(false)
+    }
+
+    fn new(directed: bool) -> Graph<N> {
+        Graph {
+            directed,
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            outgoing: Vec::new(),
+            incoming: Vec::new(),
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.index.contains_key(node)
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.nodes.iter()
+    }
+
+    pub fn add_node(&mut self, node: N) {
+        self.insert(node);
+    }
+
+    /// Adds an edge of weight 1, and its nodes if they are new.
+    pub fn add_edge(&mut self, from: N, to: N) {
+        self.add_weighted_edge(from, to, 1);
+    }
+
+    pub fn add_weighted_edge(&mut self, from: N, to: N, weight: u64) {
+        let from = self.insert(from);
+        let to = self.insert(to);
+
+        self.outgoing[from].push((to, weight));
+        self.incoming[to].push((from, weight));
+        if !self.directed && from != to {
+            self.outgoing[to].push((from, weight));
+            self.incoming[from].push((to, weight));
+        }
+    }
+
+    /// The nodes `node` has an edge to, with the edges' weights.
+    pub fn edges<'a>(&'a self, node: &N) -> impl Iterator<Item = (&'a N, u64)> + 'a {
+        Self::follow(&self.nodes, &self.outgoing, self.index.get(node).copied())
+    }
+
+    /// The nodes `node` has an edge to.
+    pub fn neighbours<'a>(&'a self, node: &N) -> impl Iterator<Item = &'a N> + 'a {
+        self.edges(node).map(|(node, _)| node)
+    }
+
+    /// The nodes with an edge to `node`, the same as its neighbours if the
+    /// graph is undirected.
+    pub fn predecessors<'a>(&'a self, node: &N) -> impl Iterator<Item = &'a N> + 'a {
+        Self::follow(&self.nodes, &self.incoming, self.index.get(node).copied())
+            .map(|(node, _)| node)
+    }
+
+    /// The nodes joined to `node` by an edge in either direction. A node
+    /// with edges both ways appears twice.
+    pub fn adjacent<'a>(&'a self, node: &N) -> impl Iterator<Item = &'a N> + 'a {
+        let incoming = if self.directed {
+            Some(self.predecessors(node))
+        } else {
+            None
+        };
+        self.neighbours(node).chain(incoming.into_iter().flatten())
+    }
+
+    /// The number of edges from `start` to every node it reaches.
+    pub fn bfs(&self, start: &N) -> HashMap<N, usize> {
+        bfs(start.clone(), |node| self.neighbours(node).cloned())
+    }
+
+    /// The lightest path from `from` to `to`, found with Dijkstra's
+    /// algorithm.
+    pub fn shortest_path(&self, from: &N, to: &N) -> Option<Path<N>> {
+        self.a_star(from, to, |_| 0)
+    }
+
+    /// Like `shortest_path`, guided by a `heuristic` that must never
+    /// overestimate the remaining weight to `to`.
+    pub fn a_star<H>(&self, from: &N, to: &N, heuristic: H) -> Option<Path<N>>
+    where
+        H: FnMut(&N) -> u64,
+    {
+        if !self.contains(from) {
+            return None;
+        }
+
+        a_star(
+            from.clone(),
+            |node| node == to,
+            |node| {
+                self.edges(node)
+                    .map(|(node, weight)| (node.clone(), weight))
+            },
+            heuristic,
+        )
+    }
+
+    /// Orders the nodes so that every edge goes forwards, keeping the order
+    /// they were added where the edges allow it. `None` if there is a
+    /// cycle, which any edge of an undirected graph is.
+    pub fn topological_sort(&self) -> Option<Vec<N>> {
+        let mut in_degree = self.incoming.iter().map(Vec::len).collect::<Vec<usize>>();
+        let mut ready = (0..self.len())
+            .filter(|node| in_degree[*node] == 0)
+            .collect::<VecDeque<usize>>();
+
+        let mut sorted = Vec::with_capacity(self.len());
+        while let Some(node) = ready.pop_front() {
+            sorted.push(self.nodes[node].clone());
+            for (next, _) in &self.outgoing[node] {
+                in_degree[*next] -= 1;
+                if in_degree[*next] == 0 {
+                    ready.push_back(*next);
+                }
+            }
+        }
+
+        if sorted.len() == self.len() {
+            Some(sorted)
+        } else {
+            None
+        }
+    }
+
+    /// The nodes of some cycle in the order its edges follow them, if there
+    /// is one. In an undirected graph going back along the same edge does
+    /// not count.
+    pub fn find_cycle(&self) -> Option<Vec<N>> {
+        const UNVISITED: u8 = 0;
+        const ON_STACK: u8 = 1;
+        const DONE: u8 = 2;
+
+        let mut state = vec![UNVISITED; self.len()];
+        for root in 0..self.len() {
+            if state[root] != UNVISITED {
+                continue;
+            }
+
+            // Each entry is a node, how many of its edges have been followed
+            // and, when undirected, whether the edge back to its parent has
+            // been skipped.
+            let mut stack = vec![(root, 0, self.directed)];
+            state[root] = ON_STACK;
+            while let Some(&(node, followed, skipped)) = stack.last() {
+                let next = match self.outgoing[node].get(followed) {
+                    Some((next, _)) => *next,
+                    None => {
+                        state[node] = DONE;
+                        stack.pop();
+                        continue;
+                    }
+                };
+
+                let parent = stack.len().checked_sub(2).map(|parent| stack[parent].0);
+                let top = stack.len() - 1;
+                stack[top].1 += 1;
+                if !skipped && Some(next) == parent {
+                    stack[top].2 = true;
+                } else if state[next] == ON_STACK {
+                    let start = stack.iter().position(|(node, _, _)| *node == next)?;
+                    return Some(
+                        stack[start..]
+                            .iter()
+                            .map(|(node, _, _)| self.nodes[*node].clone())
+                            .collect(),
+                    );
+                } else if state[next] == UNVISITED {
+                    state[next] = ON_STACK;
+                    stack.push((next, 0, self.directed));
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.find_cycle().is_some()
+    }
+
+    /// The groups of nodes joined by edges in either direction, each in the
+    /// order it was explored.
+    pub fn components(&self) -> Vec<Vec<N>> {
+        let mut seen = vec![false; self.len()];
+        let mut components = Vec::new();
+
+        for root in 0..self.len() {
+            if seen[root] {
+                continue;
+            }
+
+            seen[root] = true;
+            let mut component = vec![root];
+            let mut explored = 0;
+            while let Some(&node) = component.get(explored) {
+                explored += 1;
+                for (next, _) in self.outgoing[node].iter().chain(&self.incoming[node]) {
+                    if !seen[*next] {
+                        seen[*next] = true;
+                        component.push(*next);
+                    }
+                }
+            }
+
+            components.push(
+                component
+                    .into_iter()
+                    .map(|node| self.nodes[node].clone())
+                    .collect(),
+            );
+        }
+
+        components
+    }
+
+    fn insert(&mut self, node: N) -> usize {
+        if let Some(index) = self.index.get(&node) {
+            return *index;
+        }
+
+        let index = self.nodes.len();
+        self.index.insert(node.clone(), index);
+        self.nodes.push(node);
+        self.outgoing.push(Vec::new());
+        self.incoming.push(Vec::new());
+        index
+    }
+
+    fn follow<'a>(
+        nodes: &'a [N],
+        edges: &'a [Vec<(usize, u64)>],
+        node: Option<usize>,
+    ) -> impl Iterator<Item = (&'a N, u64)> + 'a {
+        node.into_iter()
+            .flat_map(move |node| edges[node].iter())
+            .map(move |(next, weight)| (&nodes[*next], *weight))
+    }
+}
+
+/// The number of steps from `start` to every node it reaches.
+pub fn bfs<N, F, I>(start: N, mut neighbours: F) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    distances.insert(start.clone(), 0);
+    queue.push_back((start, 0));
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in neighbours(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// The lightest path from `start` to any node satisfying `is_goal`, where
+/// `neighbours` gives each node's edges and their weights.
+pub fn dijkstra<N, G, F, I>(start: N, is_goal: G, neighbours: F) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    G: FnMut(&N) -> bool,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    a_star(start, is_goal, neighbours, |_| 0)
+}
+
+/// Like `dijkstra`, searching first where `heuristic` estimates the goal is
+/// closest. The heuristic must never overestimate the remaining weight, or
+/// the path found may not be the lightest.
+pub fn a_star<N, G, F, I, H>(
+    start: N,
+    mut is_goal: G,
+    mut neighbours: F,
+    mut heuristic: H,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    G: FnMut(&N) -> bool,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    H: FnMut(&N) -> u64,
+{
+    let mut open = BinaryHeap::new();
+    open.push(Reverse((heuristic(&start), 0, 0)));
+
+    let mut index = HashMap::new();
+    index.insert(start.clone(), 0);
+    let mut nodes: Vec<N> = vec![start];
+    let mut costs: Vec<u64> = vec![0];
+    let mut previous: Vec<Option<usize>> = vec![None];
+
+    while let Some(Reverse((_, cost, current))) = open.pop() {
+        if cost > costs[current] {
+            continue;
+        }
+
+        if is_goal(&nodes[current]) {
+            let mut path = vec![nodes[current].clone()];
+            let mut node = current;
+            while let Some(before) = previous[node] {
+                path.push(nodes[before].clone());
+                node = before;
+            }
+            path.reverse();
+
+            return Some(Path { cost, nodes: path });
+        }
+
+        for (next, weight) in neighbours(&nodes[current]) {
+            let cost = cost + weight;
+            let next = match index.get(&next).copied() {
+                Some(known) if cost >= costs[known] => continue,
+                Some(known) => {
+                    costs[known] = cost;
+                    previous[known] = Some(current);
+                    known
+                }
+                None => {
+                    index.insert(next.clone(), nodes.len());
+                    nodes.push(next);
+                    costs.push(cost);
+                    previous.push(Some(current));
+                    nodes.len() - 1
+                }
+            };
+
+            open.push(Reverse((cost + heuristic(&nodes[next]), cost, next)));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod searches {
+    use super::*;
+
+    fn graph(directed: bool, edges: &[(&'static str, &'static str, u64)]) -> Graph<&'static str> {
+        let mut graph = Graph::new(directed);
+        for (from, to, weight) in edges {
+            graph.add_weighted_edge(*from, *to, *weight);
+        }
+        graph
+    }
+
+    #[test]
+    fn edges() {
+        let directed = graph(true, &[("a", "b", 1), ("c", "a", 2)]);
+        assert_eq!(directed.neighbours(&"a").collect::<Vec<_>>(), vec![&"b"]);
+        assert_eq!(directed.predecessors(&"a").collect::<Vec<_>>(), vec![&"c"]);
+        assert_eq!(directed.adjacent(&"a").count(), 2);
+        assert_eq!(directed.neighbours(&"z").count(), 0);
+
+        let undirected = graph(false, &[("a", "b", 1), ("c", "a", 2)]);
+        assert_eq!(
+            undirected.edges(&"a").collect::<Vec<_>>(),
+            vec![(&"b", 1), (&"c", 2)]
+        );
+        assert_eq!(undirected.adjacent(&"a").count(), 2);
+        assert_eq!(undirected.len(), 3);
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let graph = graph(
+            true,
+            &[("a", "b", 7), ("a", "c", 2), ("c", "b", 3), ("b", "d", 1)],
+        );
+
+        assert_eq!(graph.bfs(&"a")[&"d"], 2);
+        assert_eq!(
+            graph.shortest_path(&"a", &"d"),
+            Some(Path {
+                cost: 6,
+                nodes: vec!["a", "c", "b", "d"],
+            })
+        );
+        assert_eq!(graph.shortest_path(&"d", &"a"), None);
+        assert_eq!(
+            graph.a_star(&"a", &"d", |node| if *node == "d" { 0 } else { 1 }),
+            graph.shortest_path(&"a", &"d")
+        );
+    }
+
+    #[test]
+    fn implicit() {
+        // Steps of 1 or 3 along the number line, where 3 costs 2.
+        let neighbours = |n: &i64| vec![(n + 1, 1), (n + 3, 2)];
+        let path = dijkstra(0, |n| *n == 6, neighbours).unwrap();
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.nodes, vec![0, 3, 6]);
+
+        let path = a_star(0, |n| *n == 6, neighbours, |n| (6 - n).max(0) as u64 / 3).unwrap();
+        assert_eq!(path, dijkstra(0, |n| *n == 6, neighbours).unwrap());
+
+        let distances = bfs(0, |n| if *n < 5 { vec![n + 1] } else { vec![] });
+        assert_eq!(distances.len(), 6);
+        assert_eq!(distances[&5], 5);
+    }
+
+    #[test]
+    fn ordering() {
+        let tasks = graph(
+            true,
+            &[
+                ("wake", "dress", 1),
+                ("shower", "dress", 1),
+                ("wake", "shower", 1),
+            ],
+        );
+        assert_eq!(
+            tasks.topological_sort(),
+            Some(vec!["wake", "shower", "dress"])
+        );
+        assert_eq!(tasks.find_cycle(), None);
+
+        let mut looped = tasks.clone();
+        looped.add_edge("dress", "wake");
+        assert_eq!(looped.topological_sort(), None);
+        assert_eq!(looped.find_cycle(), Some(vec!["wake", "dress"]));
+
+        let path = graph(false, &[("a", "b", 1), ("b", "c", 1)]);
+        assert!(!path.has_cycle());
+        assert_eq!(path.topological_sort(), None);
+
+        let mut ring = path.clone();
+        ring.add_edge("c", "a");
+        assert_eq!(ring.find_cycle(), Some(vec!["a", "b", "c"]));
+
+        let mut self_loop = Graph::directed();
+        self_loop.add_edge(1, 1);
+        assert_eq!(self_loop.find_cycle(), Some(vec![1]));
+    }
+
+    #[test]
+    fn components() {
+        let mut graph = graph(true, &[("a", "b", 1), ("c", "b", 1), ("d", "e", 1)]);
+        graph.add_node("f");
+
+        assert_eq!(
+            graph.components(),
+            vec![vec!["a", "b", "c"], vec!["d", "e"], vec!["f"]]
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Names a directory holding `day_XX` input files, searched before the
+/// default inputs. Its subdirectories hold named inputs, as the default
+/// directory's do.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where the inputs shipped with the repository live, with each
+/// subdirectory holding another person's `day_XX` files named after it.
+pub const DEFAULT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/inputs");
+
+/// Where to read a day's puzzle input from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// A file holding the input, or a directory holding `day_XX` files.
+    Path(PathBuf),
+    Stdin,
+    /// The directory named by `INPUT_DIR_VAR` if set, then the inputs
+    /// embedded by the `embed-inputs` feature or, without it, the
+    /// repository's `src/inputs`.
+    Default,
+    /// One of several people's inputs, a directory holding `day_XX` files
+    /// whose answers are recorded under `name`.
+    Named {
+        name: String,
+        dir: PathBuf,
+    },
+}
+
+/// Why a day's input could not be read.
+#[derive(Debug)]
+pub enum InputError {
+    /// None of the places the input could be in had it.
+    NotFound {
+        day: u8,
+        looked_in: Vec<PathBuf>,
+    },
+    /// The input exists but could not be read.
+    Read {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    Stdin(std::io::Error),
+}
+
+impl std::fmt::Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotFound { day, looked_in } => {
+                write!(f, "No input found for day {}, looked in:", day)?;
+                for path in looked_in {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                Ok(())
+            }
+            InputError::Read { path, error } => {
+                write!(f, "Failed to read input file {}: {}", path.display(), error)
+            }
+            InputError::Stdin(error) => write!(f, "Failed to read input from stdin: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl Source {
+    /// `-` means stdin, anything else a path.
+    pub fn from_arg(arg: &str) -> Source {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::Path(PathBuf::from(path)),
+        }
+    }
+
+    /// Whether this source can only provide the input of a single day.
+    pub fn is_single(&self) -> bool {
+        match self {
+            Source::Path(path) => !path.is_dir(),
+            Source::Stdin => true,
+            Source::Default | Source::Named { .. } => false,
+        }
+    }
+
+    /// Names the input of `day`, under which its answers are recorded. Only
+    /// the repository's own inputs are named `day_XX`, so that any other
+    /// file is named by its path and never checked against their answers.
+    pub fn name(&self, day: u8) -> String {
+        let path = match self {
+            Source::Path(dir) if dir.is_dir() => dir.join(file_name(day)),
+            Source::Path(path) => path.clone(),
+            Source::Stdin => return "stdin".to_string(),
+            Source::Default => match find_env(day, env_dir()) {
+                Some(path) => path,
+                None => return file_name(day),
+            },
+            Source::Named { name, .. } => return name.clone(),
+        };
+
+        shipped_name(&path).unwrap_or_else(|| path.display().to_string())
+    }
+
+    pub fn read(&self, day: u8) -> Result<String, InputError> {
+        match self {
+            Source::Path(dir) | Source::Named { dir, .. } if dir.is_dir() => {
+                find(day, std::slice::from_ref(dir))
+                    .map_err(|looked_in| InputError::NotFound { day, looked_in })
+                    .and_then(|path| read_file(&path))
+            }
+            Source::Path(path) if path.exists() => read_file(path),
+            Source::Path(path) | Source::Named { dir: path, .. } => Err(InputError::NotFound {
+                day,
+                looked_in: vec![path.clone()],
+            }),
+            Source::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(InputError::Stdin)?;
+                Ok(input)
+            }
+            Source::Default => read_default(day, env_dir(), embedded(day)),
+        }
+    }
+}
+
+/// Every input of `day`: the default one, then the named inputs found in
+/// the directory named by `INPUT_DIR_VAR` and the repository's inputs.
+pub fn all(day: u8) -> Result<Vec<Source>, InputError> {
+    let dirs = env_dir()
+        .into_iter()
+        .chain(std::iter::once(PathBuf::from(DEFAULT_DIR)))
+        .collect::<Vec<PathBuf>>();
+
+    let mut sources = vec![Source::Default];
+    sources.extend(named(day, &dirs)?);
+    Ok(sources)
+}
+
+/// The named inputs in the subdirectories of `dirs` holding the input of
+/// `day`, sorted by name. A name in more than one of `dirs` is read from
+/// the first.
+fn named(day: u8, dirs: &[PathBuf]) -> Result<Vec<Source>, InputError> {
+    let mut sources = Vec::new();
+    for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+        let entries = std::fs::read_dir(dir).map_err(|error| InputError::Read {
+            path: dir.clone(),
+            error,
+        })?;
+
+        for entry in entries {
+            let path = entry
+                .map_err(|error| InputError::Read {
+                    path: dir.clone(),
+                    error,
+                })?
+                .path();
+            let name = match path.file_name() {
+                Some(name) => name.to_string_lossy().into_owned(),
+                None => continue,
+            };
+
+            let known = sources.iter().any(|source| match source {
+                Source::Named { name: known, .. } => *known == name,
+                _ => false,
+            });
+            if !known && path.join(file_name(day)).is_file() {
+                sources.push(Source::Named { name, dir: path });
+            }
+        }
+    }
+
+    sources.sort_by_key(|source| source.name(day));
+    Ok(sources)
+}
+
+/// The directory named by `INPUT_DIR_VAR`, if it is set.
+fn env_dir() -> Option<PathBuf> {
+    std::env::var_os(INPUT_DIR_VAR).map(PathBuf::from)
+}
+
+/// The input file of `day` in `env_dir`, if it has one.
+fn find_env(day: u8, env_dir: Option<PathBuf>) -> Option<PathBuf> {
+    env_dir
+        .map(|dir| dir.join(file_name(day)))
+        .filter(|path| path.is_file())
+}
+
+/// The file name of `path` if it is one of the repository's own inputs.
+fn shipped_name(path: &Path) -> Option<String> {
+    let path = path.canonicalize().ok()?;
+    if path.parent()? == Path::new(DEFAULT_DIR).canonicalize().ok()? {
+        Some(path.file_name()?.to_string_lossy().into_owned())
+    } else {
+        None
+    }
+}
+
+/// Reads the input from `env_dir`, falling back on the `embedded` input if
+/// there is one, or on the repository's inputs.
+fn read_default(
+    day: u8,
+    env_dir: Option<PathBuf>,
+    embedded: Option<&str>,
+) -> Result<String, InputError> {
+    let mut dirs = env_dir.into_iter().collect::<Vec<PathBuf>>();
+    if embedded.is_none() {
+        dirs.push(PathBuf::from(DEFAULT_DIR));
+    }
+
+    match (find(day, &dirs), embedded) {
+        (Ok(path), _) => read_file(&path),
+        (Err(_), Some(input)) => Ok(input.to_string()),
+        (Err(looked_in), None) => Err(InputError::NotFound { day, looked_in }),
+    }
+}
+
+/// The first of `dirs` holding the day's input file, or every path tried.
+fn find(day: u8, dirs: &[PathBuf]) -> Result<PathBuf, Vec<PathBuf>> {
+    let paths = dirs
+        .iter()
+        .map(|dir| dir.join(file_name(day)))
+        .collect::<Vec<PathBuf>>();
+
+    match paths.iter().find(|path| path.is_file()) {
+        Some(path) => Ok(path.clone()),
+        None => Err(paths),
+    }
+}
+
+fn file_name(day: u8) -> String {
+    format!("day_{:02}", day)
+}
+
+fn read_file(path: &Path) -> Result<String, InputError> {
+    std::fs::read_to_string(path).map_err(|error| InputError::Read {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+fn embedded(day: u8) -> Option<&'static str> {
+    crate::days::get(day).and_then(|day| day.input)
+}
+
+#[cfg(test)]
+mod sources {
+    use super::*;
+    use crate::testing::TempDir;
+
+    #[test]
+    fn paths() {
+        let dir = TempDir::new("paths", &[("day_03", "three")]);
+
+        assert_eq!(Source::Path(dir.to_path_buf()).read(3).unwrap(), "three");
+        assert_eq!(Source::Path(dir.join("day_03")).read(9).unwrap(), "three");
+        assert!(Source::Path(dir.join("day_03")).is_single());
+        assert!(!Source::Path(dir.to_path_buf()).is_single());
+
+        let error = Source::Path(dir.to_path_buf()).read(4).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "No input found for day 4, looked in:\n  {}",
+                dir.join("day_04").display()
+            )
+        );
+    }
+
+    #[test]
+    fn defaults() {
+        let dir = TempDir::new("defaults", &[("day_01", "override")]);
+
+        assert_eq!(
+            read_default(1, Some(dir.to_path_buf()), None).unwrap(),
+            "override"
+        );
+        assert_eq!(
+            read_default(7, Some(dir.to_path_buf()), None).unwrap(),
+            std::fs::read_to_string(Path::new(DEFAULT_DIR).join("day_07")).unwrap()
+        );
+        assert_eq!(
+            read_default(7, Some(dir.to_path_buf()), Some("embedded")).unwrap(),
+            "embedded"
+        );
+
+        let error = read_default(30, Some(dir.to_path_buf()), None).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "No input found for day 30, looked in:\n  {}\n  {}",
+                dir.join("day_30").display(),
+                Path::new(DEFAULT_DIR).join("day_30").display()
+            )
+        );
+    }
+
+    #[test]
+    fn named_inputs() {
+        let first = TempDir::new("named-first", &[]);
+        let second = TempDir::new("named-second", &[]);
+        for (dir, name, days) in [
+            (&first, "bob", &[(1, "bob's")][..]),
+            (&first, "alice", &[(1, "alice's"), (2, "alice's")][..]),
+            (&second, "alice", &[(1, "shadowed")][..]),
+            (&second, "carol", &[(2, "carol's")][..]),
+        ] {
+            std::fs::create_dir_all(dir.join(name)).unwrap();
+            for (day, input) in days {
+                std::fs::write(dir.join(name).join(file_name(*day)), input).unwrap();
+            }
+        }
+        std::fs::write(first.join("day_01"), "not a named input").unwrap();
+
+        let dirs = [
+            first.to_path_buf(),
+            second.to_path_buf(),
+            first.join("missing"),
+        ];
+        let names = |day| {
+            named(day, &dirs)
+                .unwrap()
+                .iter()
+                .map(|source| (source.name(day), source.read(day).unwrap()))
+                .collect::<Vec<(String, String)>>()
+        };
+
+        assert_eq!(
+            names(1),
+            vec![
+                ("alice".to_string(), "alice's".to_string()),
+                ("bob".to_string(), "bob's".to_string())
+            ]
+        );
+        assert_eq!(
+            names(2),
+            vec![
+                ("alice".to_string(), "alice's".to_string()),
+                ("carol".to_string(), "carol's".to_string())
+            ]
+        );
+        assert!(names(3).is_empty());
+
+        let alice = Source::Named {
+            name: "alice".to_string(),
+            dir: first.join("alice"),
+        };
+        assert!(!alice.is_single());
+        assert!(alice.read(3).is_err());
+    }
+
+    #[test]
+    fn names() {
+        let dir = TempDir::new("names", &[("day_07", "other"), ("example", "")]);
+
+        assert_eq!(Source::Stdin.name(7), "stdin");
+        assert_eq!(Source::Path(PathBuf::from(DEFAULT_DIR)).name(7), "day_07");
+        assert_eq!(
+            Source::Path(Path::new(DEFAULT_DIR).join("day_03")).name(7),
+            "day_03"
+        );
+        assert_eq!(
+            Source::Path(dir.to_path_buf()).name(7),
+            dir.join("day_07").display().to_string()
+        );
+        assert_eq!(
+            Source::Path(dir.join("example")).name(7),
+            dir.join("example").display().to_string()
+        );
+    }
+
+    #[test]
+    fn env_dirs() {
+        let dir = TempDir::new("env-dirs", &[("day_01", "4")]);
+
+        assert_eq!(
+            find_env(1, Some(dir.to_path_buf())),
+            Some(dir.join("day_01"))
+        );
+        assert_eq!(find_env(7, Some(dir.to_path_buf())), None);
+        assert_eq!(find_env(1, None), None);
+
+        assert_eq!(shipped_name(&dir.join("day_01")), None);
+        let shipped = find_env(1, Some(PathBuf::from(DEFAULT_DIR))).unwrap();
+        assert_eq!(shipped_name(&shipped), Some("day_01".to_string()));
+    }
+
+    #[test]
+    fn from_arg() {
+        assert_eq!(Source::from_arg("-"), Source::Stdin);
+        assert_eq!(
+            Source::from_arg("inputs/day_01"),
+            Source::Path(PathBuf::from("inputs/day_01"))
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use crate::Answer;
+
+/// A JSON value, written compactly by its `Display` implementation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Integer(i128),
+    String(String),
+    Array(Vec<Json>),
+    /// Members in the order they are written.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object<K: Into<String>>(members: Vec<(K, Json)>) -> Json {
+        Json::Object(
+            members
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+}
+
+impl std::fmt::Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::Integer(value) => write!(f, "{}", value),
+            Json::String(value) => write_string(f, value),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(members) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in members.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut std::fmt::Formatter<'_>, value: &str) -> std::fmt::Result {
+    write!(f, "\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Json {
+        Json::Bool(value)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Json {
+        Json::String(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Json {
+        Json::String(value)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Json {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+/// Integers as numbers, text as a string and images as an array of rows
+/// drawn as in the answer's `Display`.
+impl From<&Answer> for Json {
+    fn from(answer: &Answer) -> Json {
+        match answer {
+            Answer::Integer(value) => Json::Integer(*value),
+            Answer::Text(text) => text.as_str().into(),
+            Answer::Image(_) => Json::Array(answer.to_string().lines().map(Json::from).collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod encoding {
+    use super::*;
+
+    #[test]
+    fn values() {
+        assert_eq!(Json::Null.to_string(), "null");
+        assert_eq!(Json::from(true).to_string(), "true");
+        assert_eq!(Json::Integer(-42).to_string(), "-42");
+        assert_eq!(Json::from(None::<&str>).to_string(), "null");
+        assert_eq!(
+            Json::from("say \"hi\"\\\n\t\u{1}").to_string(),
+            r#""say \"hi\"\\\n\t\u0001""#
+        );
+    }
+
+    #[test]
+    fn nested() {
+        let json = Json::object(vec![
+            ("day", Json::Integer(8)),
+            ("rows", Json::Array(vec!["X ".into(), " X".into()])),
+            ("empty", Json::Object(Vec::new())),
+        ]);
+
+        assert_eq!(
+            json.to_string(),
+            r#"{"day":8,"rows":["X "," X"],"empty":{}}"#
+        );
+    }
+
+    #[test]
+    fn answers() {
+        assert_eq!(Json::from(&Answer::Integer(7)), Json::Integer(7));
+        assert_eq!(
+            Json::from(&Answer::Text("CODE".to_string())),
+            Json::from("CODE")
+        );
+        assert_eq!(
+            Json::from(&Answer::Image(vec![vec![true, false], vec![false, true]])).to_string(),
+            r#"["X "," X"]"#
+        );
+    }
+}
@@ -0,0 +1,17 @@
+#![allow(clippy::upper_case_acronyms)]
+
+pub mod answers;
+pub mod bench;
+pub mod days;
+pub mod fetch;
+pub mod geometry;
+pub mod graph;
+pub mod input;
+pub mod json;
+pub mod math;
+pub mod parse;
+pub mod solution;
+#[cfg(test)]
+mod testing;
+
+pub use solution::{solve_part_1, solve_part_2, Answer, Error, Solution};
@@ -0,0 +1,342 @@
+//! Number theory over `i64` and `i128`: greatest common divisors, modular
+//! arithmetic that cannot overflow, and linear maps modulo a number such as
+//! the position of a card after a shuffle.
+//!
+//! Every modular result is in `0..modulus`, and every modulus must be
+//! positive.
+
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// The signed integers these functions work on.
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+
+    fn rem_euclid(self, modulus: Self) -> Self;
+
+    /// `(self + other) % modulus`, without overflowing however large the
+    /// sum is.
+    fn add_mod(self, other: Self, modulus: Self) -> Self;
+
+    /// `self * other % modulus`, without overflowing however large the
+    /// product is.
+    fn mul_mod(self, other: Self, modulus: Self) -> Self;
+}
+
+impl Integer for i64 {
+    const ZERO: i64 = 0;
+    const ONE: i64 = 1;
+
+    fn abs(self) -> i64 {
+        i64::abs(self)
+    }
+
+    fn rem_euclid(self, modulus: i64) -> i64 {
+        i64::rem_euclid(self, modulus)
+    }
+
+    fn add_mod(self, other: i64, modulus: i64) -> i64 {
+        (self as i128 + other as i128).rem_euclid(modulus as i128) as i64
+    }
+
+    fn mul_mod(self, other: i64, modulus: i64) -> i64 {
+        (self as i128 * other as i128).rem_euclid(modulus as i128) as i64
+    }
+}
+
+impl Integer for i128 {
+    const ZERO: i128 = 0;
+    const ONE: i128 = 1;
+
+    fn abs(self) -> i128 {
+        i128::abs(self)
+    }
+
+    fn rem_euclid(self, modulus: i128) -> i128 {
+        i128::rem_euclid(self, modulus)
+    }
+
+    fn add_mod(self, other: i128, modulus: i128) -> i128 {
+        // Both terms are reduced first, so that the gap between either and
+        // the modulus fits in an i128.
+        let a = self.rem_euclid(modulus);
+        let b = other.rem_euclid(modulus);
+        if a >= modulus - b {
+            a - (modulus - b)
+        } else {
+            a + b
+        }
+    }
+
+    fn mul_mod(self, other: i128, modulus: i128) -> i128 {
+        if let Some(product) = self.checked_mul(other) {
+            return product.rem_euclid(modulus);
+        }
+
+        // Double and add, where both terms are below the modulus and so
+        // their sum fits in a u128.
+        let modulus = modulus as u128;
+        let mut a = self.rem_euclid(modulus as i128) as u128;
+        let mut b = other.rem_euclid(modulus as i128) as u128;
+        let mut product = 0;
+        while b > 0 {
+            if b & 1 == 1 {
+                product = (product + a) % modulus;
+            }
+            a = (a + a) % modulus;
+            b >>= 1;
+        }
+
+        product as i128
+    }
+}
+
+/// The greatest common divisor, never negative, and 0 only if both are 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != T::ZERO {
+        let rem = a % b;
+        a = b;
+        b = rem;
+    }
+    a
+}
+
+/// The least common multiple, never negative, and 0 if either is 0.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        T::ZERO
+    } else {
+        (a / gcd(a, b) * b).abs()
+    }
+}
+
+/// The greatest common divisor of all of `values`, 0 if there are none.
+pub fn gcd_all<T: Integer>(values: &[T]) -> T {
+    values.iter().fold(T::ZERO, |acc, value| gcd(acc, *value))
+}
+
+/// The least common multiple of all of `values`, 1 if there are none.
+pub fn lcm_all<T: Integer>(values: &[T]) -> T {
+    values.iter().fold(T::ONE, |acc, value| lcm(acc, *value))
+}
+
+/// `(g, x, y)` such that `a * x + b * y == g`, where `g` is `gcd(a, b)`.
+pub fn extended_gcd<T: Integer>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let quotient = old_r / r;
+        let next_r = old_r - quotient * r;
+        let next_x = old_x - quotient * x;
+        let next_y = old_y - quotient * y;
+        old_r = std::mem::replace(&mut r, next_r);
+        old_x = std::mem::replace(&mut x, next_x);
+        old_y = std::mem::replace(&mut y, next_y);
+    }
+
+    if old_r < T::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` for which `a * x` is 1 modulo `modulus`, if `a` and `modulus`
+/// are coprime.
+pub fn mod_inverse<T: Integer>(a: T, modulus: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    if g == T::ONE {
+        Some(x.rem_euclid(modulus))
+    } else {
+        None
+    }
+}
+
+pub fn add_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    a.add_mod(b, modulus)
+}
+
+pub fn mul_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    a.mul_mod(b, modulus)
+}
+
+/// `base` to the power `exp`, modulo `modulus`.
+pub fn pow_mod<T: Integer>(base: T, mut exp: u128, modulus: T) -> T {
+    let mut base = base.rem_euclid(modulus);
+    let mut result = T::ONE.rem_euclid(modulus);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result.mul_mod(base, modulus);
+        }
+        base = base.mul_mod(base, modulus);
+        exp >>= 1;
+    }
+    result
+}
+
+/// The map `x -> (a * x + b) % modulus`, such as one step of a linear
+/// congruential generator or a shuffle of `modulus` cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Linear<T> {
+    pub a: T,
+    pub b: T,
+    pub modulus: T,
+}
+
+impl<T: Integer> Linear<T> {
+    pub fn new(a: T, b: T, modulus: T) -> Linear<T> {
+        Linear {
+            a: a.rem_euclid(modulus),
+            b: b.rem_euclid(modulus),
+            modulus,
+        }
+    }
+
+    /// The map that leaves every `x` where it is.
+    pub fn identity(modulus: T) -> Linear<T> {
+        Linear::new(T::ONE, T::ZERO, modulus)
+    }
+
+    pub fn apply(&self, x: T) -> T {
+        self.a
+            .mul_mod(x, self.modulus)
+            .add_mod(self.b, self.modulus)
+    }
+
+    /// The map applying `self` and then `next`.
+    pub fn then(&self, next: &Linear<T>) -> Linear<T> {
+        assert_eq!(
+            self.modulus, next.modulus,
+            "Linear maps must share a modulus to be composed"
+        );
+
+        Linear::new(
+            next.a.mul_mod(self.a, self.modulus),
+            next.apply(self.b),
+            self.modulus,
+        )
+    }
+
+    /// The map applying `self` `times` times over, by repeated squaring.
+    pub fn pow(&self, mut times: u128) -> Linear<T> {
+        let mut result = Linear::identity(self.modulus);
+        let mut square = *self;
+        while times > 0 {
+            if times & 1 == 1 {
+                result = result.then(&square);
+            }
+            square = square.then(&square);
+            times >>= 1;
+        }
+        result
+    }
+
+    /// The map undoing `self`, if `a` is invertible modulo the modulus.
+    pub fn inverse(&self) -> Option<Linear<T>> {
+        let a = mod_inverse(self.a, self.modulus)?;
+        Some(Linear::new(
+            a,
+            -a.mul_mod(self.b, self.modulus),
+            self.modulus,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod number_theory {
+    use super::*;
+
+    #[test]
+    fn divisors() {
+        assert_eq!(gcd(12i64, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0i64, 0), 0);
+        assert_eq!(lcm(4, -6i128), 12);
+        assert_eq!(lcm(0i64, 6), 0);
+        assert_eq!(gcd_all(&[12i64, 18, 27]), 3);
+        assert_eq!(lcm_all(&[18i64, 28, 44]), 2772);
+        assert_eq!(lcm_all::<i64>(&[]), 1);
+
+        let (g, x, y) = extended_gcd(240i64, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn modular() {
+        assert_eq!(mod_inverse(3i64, 11), Some(4));
+        assert_eq!(mod_inverse(-3i64, 11), Some(7));
+        assert_eq!(mod_inverse(6i64, 9), None);
+        assert_eq!(pow_mod(2i64, 10, 1000), 24);
+        assert_eq!(pow_mod(5i64, 0, 1), 0);
+
+        // Products far beyond either type, against the Mersenne prime 2^127 - 1.
+        let prime = i128::MAX;
+        assert_eq!(mul_mod(prime - 1, prime - 1, prime), 1);
+        assert_eq!(mul_mod(i64::MAX - 1, i64::MAX - 1, i64::MAX), 1);
+        assert_eq!(pow_mod(3i128, (prime - 1) as u128, prime), 1);
+        assert_eq!(mul_mod(-1i64, 1, 7), 6);
+
+        assert_eq!(add_mod(i64::MAX - 1, i64::MAX - 1, i64::MAX), i64::MAX - 2);
+        assert_eq!(add_mod(prime - 1, prime - 1, prime), prime - 2);
+        assert_eq!(add_mod(i128::MIN, -1, prime), prime - 2);
+        assert_eq!(add_mod(-3i64, 1, 7), 5);
+    }
+
+    #[test]
+    fn linear_maps() {
+        // Dealing 10 cards into a new stack reverses them, and cutting 3
+        // moves the top 3 to the bottom.
+        let reverse = Linear::new(-1i64, -1, 10);
+        let cut = Linear::new(1, -3, 10);
+        let shuffle = reverse.then(&cut);
+
+        assert_eq!(shuffle.apply(0), 6);
+        assert_eq!(shuffle.apply(9), 7);
+        assert_eq!(
+            shuffle.pow(3).apply(4),
+            shuffle.apply(shuffle.apply(shuffle.apply(4)))
+        );
+        assert_eq!(
+            shuffle.then(&shuffle.inverse().unwrap()),
+            Linear::identity(10)
+        );
+        assert_eq!(Linear::new(2i64, 1, 10).inverse(), None);
+
+        // Sums and products of terms just below the largest moduli.
+        for x in [0, 1, i64::MAX - 1] {
+            let m = i64::MAX;
+            assert_eq!(
+                Linear::new(m - 1, m - 1, m).apply(x),
+                (((m - 1) as i128 * x as i128 + (m - 1) as i128) % m as i128) as i64
+            );
+        }
+        let m = i128::MAX;
+        assert_eq!(Linear::new(m - 1, m - 1, m).apply(1), m - 2);
+        assert_eq!(Linear::new(m - 1, m - 1, m).apply(m - 1), 0);
+        assert_eq!(Linear::new(m - 1, m - 1, m).pow(2), Linear::identity(m));
+
+        let large = Linear::new(7i128, 3, 119_315_717_514_047);
+        let repeated = large.pow(101_741_582_076_661);
+        assert_eq!(
+            repeated.inverse().unwrap().apply(repeated.apply(2020)),
+            2020
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! The error every parser in the project reports, pointing at the text it
+//! could not make sense of.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Where and why an input failed to parse. Lines and columns count from 1,
+/// and columns count characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The puzzle whose input this is, if known.
+    pub day: Option<u8>,
+    pub line: usize,
+    pub column: usize,
+    /// The offending text, empty if something is missing.
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    /// An error about `text`, which must be a slice of `input` such as one
+    /// returned by `lines` or `split`, so that its position can be found.
+    pub fn at<R: Display>(input: &str, text: &str, reason: R) -> ParseError {
+        let (line, column) = position(input, text);
+
+        ParseError {
+            day: None,
+            line,
+            column,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// An error about the end of `input`, for something missing from it.
+    pub fn at_end<R: Display>(input: &str, reason: R) -> ParseError {
+        ParseError::at(input, &input[input.len()..], reason)
+    }
+
+    /// Moves an error found while parsing `inner`, a slice of `outer`, to
+    /// its position in `outer`.
+    pub fn within(mut self, outer: &str, inner: &str) -> ParseError {
+        let (line, column) = position(outer, inner);
+        if self.line == 1 {
+            self.column += column - 1;
+        }
+        self.line += line - 1;
+        self
+    }
+
+    pub fn on_day(mut self, day: u8) -> ParseError {
+        self.day = Some(day);
+        self
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {} input ", day)?;
+        }
+        write!(
+            f,
+            "{}:{}: {} at {:?}",
+            self.line, self.column, self.reason, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `text`, a slice of `input`, reporting where it is if it fails.
+/// Types that already fail with a `ParseError` should be moved into place
+/// with `ParseError::within` instead.
+pub fn value<T>(input: &str, text: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.parse().map_err(|err| ParseError::at(input, text, err))
+}
+
+/// Parses comma separated intcode of any word type, reporting which code is
+/// invalid.
+pub fn program<T: intcode::Word>(input: &str) -> Result<intcode::Program<T>, ParseError> {
+    input
+        .parse()
+        .map_err(|err: intcode::ParseProgramError| ParseError {
+            day: None,
+            line: err.line,
+            column: err.column,
+            text: err.code,
+            reason: err.error.to_string(),
+        })
+}
+
+/// The line and column `text` starts at in `input`. Slices of `input` are
+/// found by address, so that repeated text is placed correctly, and any
+/// other text at its first occurrence, or the start if it does not occur.
+fn position(input: &str, text: &str) -> (usize, usize) {
+    let address = (text.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    let inside = input
+        .len()
+        .checked_sub(text.len())
+        .is_some_and(|last| address <= last);
+    let offset = if inside && input.is_char_boundary(address) {
+        address
+    } else {
+        input.find(text).unwrap_or(0)
+    };
+
+    let before = &input[..offset];
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+#[cfg(test)]
+mod locations {
+    use super::*;
+
+    #[test]
+    fn positions() {
+        let input = "12\n34\n\n5é6";
+        let lines = input.lines().collect::<Vec<&str>>();
+
+        assert_eq!(position(input, lines[0]), (1, 1));
+        assert_eq!(position(input, &lines[1][1..]), (2, 2));
+        assert_eq!(position(input, lines[2]), (3, 1));
+        assert_eq!(position(input, &lines[3][3..]), (4, 3));
+        assert_eq!(position(input, &input[input.len()..]), (4, 4));
+        assert_eq!(position(input, "34"), (2, 1));
+        assert_eq!(position(input, "missing"), (1, 1));
+    }
+
+    #[test]
+    fn nested() {
+        let input = "a\nbb,cc\n";
+        let line = input.lines().nth(1).unwrap();
+        let field = line.split(',').nth(1).unwrap();
+
+        let err = ParseError::at(field, &field[1..], "bad").within(input, field);
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.on_day(3).to_string(), "day 3 input 2:5: bad at \"c\"");
+    }
+
+    #[test]
+    fn values() {
+        let input = "1,2,x,4";
+        assert_eq!(value::<u8>(input, &input[..1]), Ok(1));
+
+        let err = value::<u8>(input, &input[4..5]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1:5: invalid digit found in string at \"x\""
+        );
+
+        assert!(program::<i32>("1,0,0,0,99\n").is_ok());
+        let err = program::<i32>("1,0,\n0,,99").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+
+        let err = program::<i32>("104,1125899906842624,99").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 5));
+        assert!(program::<i64>("104,1125899906842624,99").is_ok());
+    }
+}
@@ -0,0 +1,117 @@
+use crate::parse::ParseError;
+use intcode::ProgramState;
+use std::fmt::{Debug, Display};
+
+/// A day's puzzle: parsed once from the input, then asked for either answer.
+pub trait Solution: Sized {
+    type Err: Debug + Display;
+
+    fn parse(input: &str) -> Result<Self, Self::Err>;
+
+    fn part_1(&self) -> Result<Answer, Self::Err>;
+
+    fn part_2(&self) -> Result<Answer, Self::Err>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// Rows of pixels, `true` where the pixel is lit.
+    Image(Vec<Vec<bool>>),
+}
+
+impl Answer {
+    /// Names the kind of answer: `integer`, `string` or `image`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Integer(_) => "integer",
+            Answer::Text(_) => "string",
+            Answer::Image(_) => "image",
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Image(rows) => {
+                let rows = rows
+                    .iter()
+                    .map(|row| row.iter().map(|&lit| if lit { 'X' } else { ' ' }).collect())
+                    .collect::<Vec<String>>();
+                write!(f, "{}", rows.join("\n"))
+            }
+        }
+    }
+}
+
+macro_rules! impl_integer_answer {
+    ($($integer:ty),*) => {
+        $(
+            impl From<$integer> for Answer {
+                fn from(value: $integer) -> Self {
+                    Answer::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_answer!(i32, u32, i64, u64, usize);
+
+/// Failures shared by most days.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The input could not be parsed.
+    Parse(ParseError),
+    /// An intcode program stopped in an unexpected state.
+    Program(ProgramState),
+    /// The input parsed, but has no answer.
+    NoAnswer(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "Failed to parse input: {}", err),
+            Error::Program(state) => write!(f, "Program stopped with {:?}", state),
+            Error::NoAnswer(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Parses `input` and solves part 1, for use as a `days::Solver`.
+pub fn solve_part_1<S: Solution>(input: &str) -> Result<Answer, String> {
+    S::parse(input)
+        .and_then(|solution| solution.part_1())
+        .map_err(|err| err.to_string())
+}
+
+/// Parses `input` and solves part 2, for use as a `days::Solver`.
+pub fn solve_part_2<S: Solution>(input: &str) -> Result<Answer, String> {
+    S::parse(input)
+        .and_then(|solution| solution.part_2())
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::from(42_u32).to_string(), "42");
+        assert_eq!(Answer::from(-7).to_string(), "-7");
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::Text("CODE".to_string()).to_string(), "CODE");
+        assert_eq!(
+            Answer::Image(vec![vec![true, false], vec![false, true]]).to_string(),
+            "X \n X"
+        );
+    }
+}